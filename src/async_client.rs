@@ -0,0 +1,1206 @@
+use futures_util::{Stream, TryStreamExt};
+
+use crate::author;
+use crate::author::Author;
+
+use crate::branch_detail;
+use crate::branches;
+use crate::builder::{ClientBuilder, Endpoints};
+use crate::commit_detail;
+use crate::commits;
+use crate::compare;
+use crate::components;
+use crate::coverage;
+use crate::errors::Error;
+use crate::file_report;
+use crate::flags;
+use crate::owner;
+use crate::pagination::{PageOptions, page_stream};
+use crate::pulls;
+use crate::report;
+use crate::repos;
+use crate::retry::RetryPolicy;
+use crate::service::Service;
+use crate::test_results;
+use crate::totals::Totals;
+use crate::transport::{AsyncHttpTransport, AsyncReqwestTransport, HttpRequest, Method};
+use crate::uploads;
+use crate::url::{encode_path, encode_segment, with_query};
+use crate::users;
+
+/**
+ * AsyncClient is a struct that represents an asynchronous client to the Codecov API.
+ * It exposes the same methods as Client, but they return futures.
+ */
+pub struct AsyncClient {
+    token: String,
+    endpoints: Endpoints,
+    retry_policy: RetryPolicy,
+    transport: Box<dyn AsyncHttpTransport>,
+}
+
+impl AsyncClient {
+    /**
+     * new_from_env reads CODECOV_OWNER_TOKEN from environment variable.
+     * If CODECOV_API_URL is set, it is used as the base URL of the API.
+     */
+    pub fn new_from_env() -> Result<AsyncClient, Error> {
+        Ok(ClientBuilder::from_env()?.build_async())
+    }
+
+    pub fn new(token: String) -> AsyncClient {
+        AsyncClient::builder(token).build_async()
+    }
+
+    pub fn builder(token: String) -> ClientBuilder {
+        ClientBuilder::new(token)
+    }
+
+    pub(crate) fn from_builder(builder: ClientBuilder) -> AsyncClient {
+        AsyncClient {
+            token: builder.token,
+            endpoints: Endpoints::new(builder.base_url),
+            retry_policy: builder.retry_policy,
+            transport: match builder.async_transport {
+                Some(transport) => transport,
+                None => Box::new(AsyncReqwestTransport::new()),
+            },
+        }
+    }
+
+    /**
+     * base_url returns the base URL of the API this client talks to.
+     */
+    pub fn base_url(&self) -> &str {
+        self.endpoints.base_url()
+    }
+
+    fn auth_header_val(&self) -> String {
+        format!("bearer {}", self.token)
+    }
+
+    /**
+     * get_all_repos returns a list of all repos for a given owner.
+     * /repos endpoint returns a list of repos for a given owner with pagination.
+     * This function will make multiple requests to get all repos.
+     */
+    pub async fn get_all_repos(&self, owner: &owner::Owner) -> Result<Vec<repos::Repo>, Error> {
+        self.stream_repos(owner, &PageOptions::new().page_size(100))
+            .try_collect()
+            .await
+    }
+
+    /**
+     * stream_repos returns a lazy stream over the repos for a given owner.
+     * Pages are requested as the stream is polled.
+     */
+    pub fn stream_repos(
+        &self,
+        owner: &owner::Owner,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<repos::Repo, Error>> + use<'_> {
+        let url = format!("{}/repos", self.endpoints.owner_endpoint(owner));
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * send makes a request and returns the JSON body.
+     * A non-success status is returned as a typed Error.
+     * Failed requests are retried according to the retry policy.
+     */
+    pub(crate) async fn send(&self, request: HttpRequest) -> Result<serde_json::Value, Error> {
+        let request = request.header("Authorization", &self.auth_header_val());
+        let mut attempt = 1;
+        loop {
+            let res = match self.transport.send(&request).await {
+                Ok(res) => res.into_json(),
+                Err(e) => Err(e),
+            };
+            match res {
+                Err(e) if self.retry_policy.should_retry(&e, attempt) => {
+                    let delay = self.retry_policy.delay(&e, attempt);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /**
+     * api_raw_json returns a serde_json::Value from a given url.
+     */
+    async fn api_raw_json(&self, url: &str) -> Result<serde_json::Value, Error> {
+        self.send(HttpRequest::get(url)).await
+    }
+
+    /**
+     * api_request returns a deserialized struct from a given url.
+     */
+    pub(crate) async fn api_request<T: serde::de::DeserializeOwned + std::fmt::Debug>(
+        &self,
+        url: &str,
+    ) -> Result<T, Error> {
+        let res = self.api_raw_json(url).await?;
+        let data = match serde_json::from_value::<T>(res) {
+            Ok(data) => data,
+            Err(e) => return Err(Error::DeserializeError(e)),
+        };
+        Ok(data)
+    }
+
+    /**
+     * api_patch sends a JSON body with PATCH to a given url and returns a deserialized struct.
+     */
+    pub(crate) async fn api_patch<T: serde::de::DeserializeOwned + std::fmt::Debug>(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<T, Error> {
+        let res = self
+            .send(HttpRequest::new(Method::Patch, url).json(body))
+            .await?;
+        let data = match serde_json::from_value::<T>(res) {
+            Ok(data) => data,
+            Err(e) => return Err(Error::DeserializeError(e)),
+        };
+        Ok(data)
+    }
+
+    /**
+     * get_commits returns a list of commits for a given author.
+     * https://docs.codecov.com/reference/repos_commits_list
+     */
+    pub async fn get_commits(
+        &self,
+        author: &author::Author,
+    ) -> Result<commits::CommitsAPIResponse, Error> {
+        let url = format!("{}/commits", self.endpoints.repos_endpoint(author));
+        self.api_request::<commits::CommitsAPIResponse>(&url).await
+    }
+
+    /**
+     * get_all_commits returns all commits for a given author, following the pagination.
+     */
+    pub async fn get_all_commits(
+        &self,
+        author: &author::Author,
+        options: &PageOptions,
+    ) -> Result<Vec<commits::Commit>, Error> {
+        self.stream_commits(author, options).try_collect().await
+    }
+
+    /**
+     * stream_commits returns a lazy stream over the commits for a given author.
+     */
+    pub fn stream_commits(
+        &self,
+        author: &author::Author,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<commits::Commit, Error>> + use<'_> {
+        let url = format!("{}/commits", self.endpoints.repos_endpoint(author));
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * get_commit returns a single commit with its full report for a given author and commit sha.
+     * https://docs.codecov.com/reference/repos_commits_retrieve
+     */
+    pub async fn get_commit(
+        &self,
+        author: &Author,
+        sha: &str,
+    ) -> Result<commit_detail::CommitDetail, Error> {
+        let url = format!("{}/commits/{}", self.endpoints.repos_endpoint(author), sha);
+        self.api_request::<commit_detail::CommitDetail>(&url).await
+    }
+
+    /**
+     * get_branches returns a list of branches for a given author.
+     * https://docs.codecov.com/reference/repos_branches_list
+     */
+    pub async fn get_branches(
+        &self,
+        author: &author::Author,
+    ) -> Result<branches::BranchesAPIResponse, Error> {
+        let url = format!("{}/branches", self.endpoints.repos_endpoint(author));
+        self.api_request::<branches::BranchesAPIResponse>(&url)
+            .await
+    }
+
+    /**
+     * get_all_branches returns all branches for a given author, following the pagination.
+     */
+    pub async fn get_all_branches(
+        &self,
+        author: &author::Author,
+        options: &PageOptions,
+    ) -> Result<Vec<branches::Branch>, Error> {
+        self.stream_branches(author, options).try_collect().await
+    }
+
+    /**
+     * stream_branches returns a lazy stream over the branches for a given author.
+     */
+    pub fn stream_branches(
+        &self,
+        author: &author::Author,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<branches::Branch, Error>> + use<'_> {
+        let url = format!("{}/branches", self.endpoints.repos_endpoint(author));
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * get_branch_detail returns a branch detail for a given author and branch name.
+     * https://docs.codecov.com/reference/repos_branches_retrieve
+     */
+    pub async fn get_branch_detail(
+        &self,
+        author: &Author,
+        branch_name: &str,
+    ) -> Result<branch_detail::BranchDetailAPISuccessResponse, Error> {
+        let url = format!(
+            "{}/branches/{}",
+            self.endpoints.repos_endpoint(author),
+            branch_name
+        );
+        self.api_request::<branch_detail::BranchDetailAPISuccessResponse>(&url)
+            .await
+    }
+
+    /**
+     * compare returns the coverage difference between two commits or of a pull request.
+     * https://docs.codecov.com/reference/repos_compare_retrieve
+     */
+    pub async fn compare(
+        &self,
+        author: &Author,
+        target: &compare::CompareTarget,
+    ) -> Result<compare::Comparison, Error> {
+        let url = format!("{}/compare", self.endpoints.repos_endpoint(author));
+        let url = with_query(&url, &target.query());
+        self.api_request::<compare::Comparison>(&url).await
+    }
+
+    /**
+     * get_pulls returns the pull requests for a given author, following the pagination.
+     * state filters the pull requests (all states if None).
+     * https://docs.codecov.com/reference/repos_pulls_list
+     */
+    pub async fn get_pulls(
+        &self,
+        author: &Author,
+        state: Option<pulls::PullState>,
+        options: &PageOptions,
+    ) -> Result<Vec<pulls::Pull>, Error> {
+        self.stream_pulls(author, state, options)
+            .try_collect()
+            .await
+    }
+
+    /**
+     * stream_pulls returns a lazy stream over the pull requests for a given author.
+     */
+    pub fn stream_pulls(
+        &self,
+        author: &Author,
+        state: Option<pulls::PullState>,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<pulls::Pull, Error>> + use<'_> {
+        let url = format!("{}/pulls", self.endpoints.repos_endpoint(author));
+        let url = with_query(
+            &url,
+            &[("state", state.map(|state| state.as_str().to_string()))],
+        );
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * get_pull returns a single pull request for a given author and pull request number.
+     * https://docs.codecov.com/reference/repos_pulls_retrieve
+     */
+    pub async fn get_pull(&self, author: &Author, pullid: u64) -> Result<pulls::Pull, Error> {
+        let url = format!("{}/pulls/{}", self.endpoints.repos_endpoint(author), pullid);
+        self.api_request::<pulls::Pull>(&url).await
+    }
+
+    /**
+     * get_flags returns the flags for a given author, following the pagination.
+     * https://docs.codecov.com/reference/repos_flags_list
+     */
+    pub async fn get_flags(
+        &self,
+        author: &Author,
+        options: &PageOptions,
+    ) -> Result<Vec<flags::Flag>, Error> {
+        self.stream_flags(author, options).try_collect().await
+    }
+
+    /**
+     * stream_flags returns a lazy stream over the flags for a given author.
+     */
+    pub fn stream_flags(
+        &self,
+        author: &Author,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<flags::Flag, Error>> + use<'_> {
+        let url = format!("{}/flags", self.endpoints.repos_endpoint(author));
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * get_flag_totals returns the latest totals of a flag for a given author.
+     * https://docs.codecov.com/reference/repos_totals_retrieve
+     */
+    pub async fn get_flag_totals(&self, author: &Author, flag_name: &str) -> Result<Totals, Error> {
+        let url = format!("{}/totals", self.endpoints.repos_endpoint(author));
+        let url = with_query(&url, &[("flag", Some(flag_name.to_string()))]);
+        let report = self.api_request::<branch_detail::Report>(&url).await?;
+        Ok(report.totals)
+    }
+
+    /**
+     * get_flag_coverage returns the coverage trend of a flag for a given author.
+     * https://docs.codecov.com/reference/repos_flags_coverage_list
+     */
+    pub async fn get_flag_coverage(
+        &self,
+        author: &Author,
+        flag_name: &str,
+        query: &coverage::CoverageQuery,
+    ) -> Result<Vec<coverage::CoveragePoint>, Error> {
+        let url = format!(
+            "{}/flags/{}/coverage",
+            self.endpoints.repos_endpoint(author),
+            encode_segment(flag_name)
+        );
+        let url = with_query(&url, &query.query());
+        page_stream(self, &url, &PageOptions::new())
+            .try_collect()
+            .await
+    }
+
+    /**
+     * get_components returns the components for a given author.
+     * https://docs.codecov.com/reference/repos_components_list
+     */
+    pub async fn get_components(
+        &self,
+        author: &Author,
+    ) -> Result<components::ComponentsAPIResponse, Error> {
+        let url = format!("{}/components", self.endpoints.repos_endpoint(author));
+        self.api_request::<components::ComponentsAPIResponse>(&url)
+            .await
+    }
+
+    /**
+     * get_component_report returns the latest report of the default branch filtered to a component.
+     * https://docs.codecov.com/reference/repos_report_retrieve
+     */
+    pub async fn get_component_report(
+        &self,
+        author: &Author,
+        component_id: &str,
+    ) -> Result<branch_detail::Report, Error> {
+        let query = report::ReportQuery::new().component_id(component_id);
+        self.get_report(author, &query).await
+    }
+
+    /**
+     * get_component_totals returns the latest totals of a component for a given author.
+     * https://docs.codecov.com/reference/repos_totals_retrieve
+     */
+    pub async fn get_component_totals(
+        &self,
+        author: &Author,
+        component_id: &str,
+    ) -> Result<Totals, Error> {
+        let url = format!("{}/totals", self.endpoints.repos_endpoint(author));
+        let url = with_query(&url, &[("component_id", Some(component_id.to_string()))]);
+        let report = self.api_request::<branch_detail::Report>(&url).await?;
+        Ok(report.totals)
+    }
+
+    /**
+     * get_coverage returns the coverage trend for a given author, aggregated by query.interval.
+     * https://docs.codecov.com/reference/repos_coverage_list
+     */
+    pub async fn get_coverage(
+        &self,
+        author: &Author,
+        query: &coverage::CoverageQuery,
+    ) -> Result<Vec<coverage::CoveragePoint>, Error> {
+        let url = format!("{}/coverage", self.endpoints.repos_endpoint(author));
+        let url = with_query(&url, &query.query());
+        page_stream(self, &url, &PageOptions::new())
+            .try_collect()
+            .await
+    }
+
+    /**
+     * get_report returns the report of a commit for a given author, filtered by query.
+     * https://docs.codecov.com/reference/repos_report_retrieve
+     */
+    pub async fn get_report(
+        &self,
+        author: &Author,
+        query: &report::ReportQuery,
+    ) -> Result<branch_detail::Report, Error> {
+        let url = format!("{}/report", self.endpoints.repos_endpoint(author));
+        let url = with_query(&url, &query.query());
+        self.api_request::<branch_detail::Report>(&url).await
+    }
+
+    /**
+     * get_report_tree returns the report for a given author as a directory tree.
+     * depth limits the levels returned below query.path. The API returns a single level by default.
+     * https://docs.codecov.com/reference/repos_report_tree_list
+     */
+    pub async fn get_report_tree(
+        &self,
+        author: &Author,
+        query: &report::ReportQuery,
+        depth: Option<usize>,
+    ) -> Result<report::ReportTreeAPIResponse, Error> {
+        let url = format!("{}/report/tree", self.endpoints.repos_endpoint(author));
+        let mut params = query.query();
+        params.push(("depth", depth.map(|depth| depth.to_string())));
+        let url = with_query(&url, &params);
+        self.api_request::<report::ReportTreeAPIResponse>(&url)
+            .await
+    }
+
+    /**
+     * get_file_report returns the line-by-line coverage of a file for a given author.
+     * https://docs.codecov.com/reference/repos_file_report_retrieve
+     */
+    pub async fn get_file_report(
+        &self,
+        author: &Author,
+        path: &str,
+        commit: &file_report::CommitRef,
+    ) -> Result<file_report::FileReport, Error> {
+        let url = format!(
+            "{}/file_report/{}",
+            self.endpoints.repos_endpoint(author),
+            encode_path(path)
+        );
+        let url = with_query(&url, &commit.query());
+        self.api_request::<file_report::FileReport>(&url).await
+    }
+
+    /**
+     * get_repo returns the detail of a repo, including the latest totals of the default branch.
+     * https://docs.codecov.com/reference/repos_retrieve
+     */
+    pub async fn get_repo(&self, author: &Author) -> Result<repos::RepoDetail, Error> {
+        let url = self.endpoints.repos_endpoint(author);
+        self.api_request::<repos::RepoDetail>(&url).await
+    }
+
+    /**
+     * get_repo_config returns the upload token and the settings of a repo.
+     * https://docs.codecov.com/reference/repos_config_retrieve
+     */
+    pub async fn get_repo_config(&self, author: &Author) -> Result<repos::RepoConfig, Error> {
+        let url = format!("{}/config", self.endpoints.repos_endpoint(author));
+        self.api_request::<repos::RepoConfig>(&url).await
+    }
+
+    /**
+     * list_owners returns the owners accessible with the token on a given service.
+     * https://docs.codecov.com/reference/service_owners_list
+     */
+    pub async fn list_owners(
+        &self,
+        service: Service,
+        options: &PageOptions,
+    ) -> Result<Vec<owner::OwnerDetail>, Error> {
+        self.stream_owners(service, options).try_collect().await
+    }
+
+    /**
+     * stream_owners returns a lazy stream over the owners accessible on a given service.
+     */
+    pub fn stream_owners(
+        &self,
+        service: Service,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<owner::OwnerDetail, Error>> + use<'_> {
+        let url = format!("{}/", self.endpoints.service_endpoint(service));
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * get_owner returns the detail of an owner.
+     * https://docs.codecov.com/reference/owners_retrieve
+     */
+    pub async fn get_owner(&self, owner: &owner::Owner) -> Result<owner::OwnerDetail, Error> {
+        let url = self.endpoints.owner_endpoint(owner);
+        self.api_request::<owner::OwnerDetail>(&url).await
+    }
+
+    /**
+     * list_users returns the users of an owner matching query, following the pagination.
+     * https://docs.codecov.com/reference/users_list
+     */
+    pub async fn list_users(
+        &self,
+        owner: &owner::Owner,
+        query: &users::UsersQuery,
+        options: &PageOptions,
+    ) -> Result<Vec<users::User>, Error> {
+        self.stream_users(owner, query, options).try_collect().await
+    }
+
+    /**
+     * stream_users returns a lazy stream over the users of an owner matching query.
+     */
+    pub fn stream_users(
+        &self,
+        owner: &owner::Owner,
+        query: &users::UsersQuery,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<users::User, Error>> + use<'_> {
+        let url = format!("{}/users", self.endpoints.owner_endpoint(owner));
+        let url = with_query(&url, &query.query());
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * get_user returns a user of an owner by username or ownerid.
+     * https://docs.codecov.com/reference/users_retrieve
+     */
+    pub async fn get_user(&self, owner: &owner::Owner, user: &str) -> Result<users::User, Error> {
+        let url = format!(
+            "{}/users/{}",
+            self.endpoints.owner_endpoint(owner),
+            encode_segment(user)
+        );
+        self.api_request::<users::User>(&url).await
+    }
+
+    /**
+     * update_user_activation activates or deactivates the seat of a user and returns the updated user.
+     * https://docs.codecov.com/reference/users_partial_update
+     */
+    pub async fn update_user_activation(
+        &self,
+        owner: &owner::Owner,
+        user: &str,
+        activated: bool,
+    ) -> Result<users::User, Error> {
+        let url = format!(
+            "{}/users/{}",
+            self.endpoints.owner_endpoint(owner),
+            encode_segment(user)
+        );
+        self.api_patch::<users::User>(&url, &serde_json::json!({ "activated": activated }))
+            .await
+    }
+
+    /**
+     * get_commit_uploads returns the uploads of a commit for a given author, following the pagination.
+     * https://docs.codecov.com/reference/repos_commits_uploads_list
+     */
+    pub async fn get_commit_uploads(
+        &self,
+        author: &Author,
+        sha: &str,
+        options: &PageOptions,
+    ) -> Result<Vec<uploads::Upload>, Error> {
+        self.stream_commit_uploads(author, sha, options)
+            .try_collect()
+            .await
+    }
+
+    /**
+     * stream_commit_uploads returns a lazy stream over the uploads of a commit.
+     */
+    pub fn stream_commit_uploads(
+        &self,
+        author: &Author,
+        sha: &str,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<uploads::Upload, Error>> + use<'_> {
+        let url = format!(
+            "{}/commits/{}/uploads",
+            self.endpoints.repos_endpoint(author),
+            sha
+        );
+        page_stream(self, &url, options)
+    }
+
+    /**
+     * compare_file returns the coverage difference of a single file.
+     * https://docs.codecov.com/reference/repos_compare_file_retrieve
+     */
+    pub async fn compare_file(
+        &self,
+        author: &Author,
+        target: &compare::CompareTarget,
+        path: &str,
+    ) -> Result<compare::ComparisonFile, Error> {
+        let url = format!(
+            "{}/compare/file/{}",
+            self.endpoints.repos_endpoint(author),
+            encode_path(path)
+        );
+        let url = with_query(&url, &target.query());
+        self.api_request::<compare::ComparisonFile>(&url).await
+    }
+
+    /**
+     * compare_segments returns the diff hunks of a single file with the base and head line coverage.
+     * https://docs.codecov.com/reference/repos_compare_segments_retrieve
+     */
+    pub async fn compare_segments(
+        &self,
+        author: &Author,
+        target: &compare::CompareTarget,
+        path: &str,
+    ) -> Result<compare::FileSegments, Error> {
+        let url = format!(
+            "{}/compare/segments/{}",
+            self.endpoints.repos_endpoint(author),
+            encode_path(path)
+        );
+        let url = with_query(&url, &target.query());
+        self.api_request::<compare::FileSegments>(&url).await
+    }
+
+    /**
+     * compare_impacted_files returns the files whose coverage changed.
+     * https://docs.codecov.com/reference/repos_compare_impacted_files_retrieve
+     */
+    pub async fn compare_impacted_files(
+        &self,
+        author: &Author,
+        target: &compare::CompareTarget,
+    ) -> Result<compare::ImpactedFiles, Error> {
+        let url = format!(
+            "{}/compare/impacted_files",
+            self.endpoints.repos_endpoint(author)
+        );
+        let url = with_query(&url, &target.query());
+        self.api_request::<compare::ImpactedFiles>(&url).await
+    }
+
+    /**
+     * compare_flags returns the coverage change per flag.
+     * https://docs.codecov.com/reference/repos_compare_flags_list
+     */
+    pub async fn compare_flags(
+        &self,
+        author: &Author,
+        target: &compare::CompareTarget,
+    ) -> Result<Vec<compare::FlagComparison>, Error> {
+        let url = format!("{}/compare/flags", self.endpoints.repos_endpoint(author));
+        let url = with_query(&url, &target.query());
+        self.api_request::<Vec<compare::FlagComparison>>(&url).await
+    }
+
+    /**
+     * compare_components returns the coverage change per component.
+     * https://docs.codecov.com/reference/repos_compare_components_list
+     */
+    pub async fn compare_components(
+        &self,
+        author: &Author,
+        target: &compare::CompareTarget,
+    ) -> Result<Vec<compare::ComponentComparison>, Error> {
+        let url = format!(
+            "{}/compare/components",
+            self.endpoints.repos_endpoint(author)
+        );
+        let url = with_query(&url, &target.query());
+        self.api_request::<Vec<compare::ComponentComparison>>(&url)
+            .await
+    }
+
+    /**
+     * get_test_results returns the test analytics for a given author, following the pagination.
+     * https://docs.codecov.com/reference/repos_test_results_list
+     */
+    pub async fn get_test_results(
+        &self,
+        author: &Author,
+        query: &test_results::TestResultsQuery,
+        options: &PageOptions,
+    ) -> Result<Vec<test_results::TestResult>, Error> {
+        self.stream_test_results(author, query, options)
+            .try_collect()
+            .await
+    }
+
+    /**
+     * stream_test_results returns a lazy stream over the test analytics for a given author.
+     */
+    pub fn stream_test_results(
+        &self,
+        author: &Author,
+        query: &test_results::TestResultsQuery,
+        options: &PageOptions,
+    ) -> impl Stream<Item = Result<test_results::TestResult, Error>> + use<'_> {
+        let url = format!("{}/test-results", self.endpoints.repos_endpoint(author));
+        let url = with_query(&url, &query.query());
+        page_stream(self, &url, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::fixtures;
+    use crate::transport::{HttpResponse, Method, MockTransport};
+
+    fn mock_client(mock: &Arc<MockTransport>) -> AsyncClient {
+        AsyncClient::builder("token".to_string())
+            .async_transport(mock.clone())
+            .retry_policy(RetryPolicy::none())
+            .build_async()
+    }
+
+    #[tokio::test]
+    async fn test_get_all_repos() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let owner = owner::Owner::new(Service::GitHub, "codecov");
+        let repos = client.get_all_repos(&owner).await.unwrap();
+        let names: Vec<&str> = repos.iter().map(|repo| repo.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["codecov-demo", "example-python", "example-rust"]
+        );
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].header_value("Authorization"),
+            Some("bearer token")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_commits() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let commits = client.get_commits(&author).await.unwrap();
+        assert!(!commits.results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_all_commits() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let commits = client
+            .get_all_commits(&author, &PageOptions::new().page_size(2))
+            .await
+            .unwrap();
+        assert_eq!(commits.len(), 5);
+
+        let options = PageOptions::new().page_size(2).max_items(3);
+        let commits = client.get_all_commits(&author, &options).await.unwrap();
+        let ids: Vec<&str> = commits.iter().map(|c| c.commitid.as_str()).collect();
+        assert_eq!(ids, vec!["aaa", "bbb", "ccc"]);
+        // 3 pages for the first call, 2 pages for the second one
+        assert_eq!(mock.requests().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_get_commit() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let commit = client
+            .get_commit(&author, "1eb341765e7c3daa88ae5d2a751538a620c6dbce")
+            .await
+            .unwrap();
+        assert_eq!(commit.commitid, "1eb341765e7c3daa88ae5d2a751538a620c6dbce");
+        let files = commit.files();
+        assert_eq!(files.len(), 1);
+        let lines = files[0].line_coverage.as_ref().unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2].number, 4);
+        assert_eq!(lines[2].coverage, file_report::LineCoverage::Miss);
+    }
+
+    #[tokio::test]
+    async fn test_get_branches() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let branches = client.get_branches(&author).await.unwrap();
+        assert!(!branches.results.is_empty());
+        let branches = client
+            .get_all_branches(&author, &PageOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(branches.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_branch_detail() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let branch_name = "main";
+        let branch_detail = client
+            .get_branch_detail(&author, branch_name)
+            .await
+            .unwrap();
+        assert_eq!(branch_detail.name, branch_name);
+        assert_eq!(branch_detail.latest_coverage(), 86.05);
+    }
+
+    #[tokio::test]
+    async fn test_get_branch_detail_not_found() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let branch_name = "aaaaaaaaaa";
+        match client.get_branch_detail(&author, branch_name).await {
+            Err(Error::NotFound { detail }) => {
+                assert_eq!(detail, "No Branch matches the given query.")
+            }
+            other => panic!("should be not found: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized() {
+        let mock = Arc::new(MockTransport::new().with_response(
+            Method::Get,
+            &fixtures::url("/github/codecov/repos/codecov-demo/branches"),
+            HttpResponse::new(401, r#"{"detail": "Invalid token."}"#),
+        ));
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        assert!(matches!(
+            client.get_branches(&author).await,
+            Err(Error::Unauthorized)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let url = fixtures::url("/github/codecov/repos/codecov-demo/branches/main");
+        let mock = Arc::new(
+            MockTransport::new()
+                .with_response(Method::Get, &url, HttpResponse::new(502, "Bad Gateway"))
+                .with_response(
+                    Method::Get,
+                    &url,
+                    HttpResponse::new(429, "").header("Retry-After", "0"),
+                )
+                .with_json(&url, fixtures::branch_detail("main")),
+        );
+        let client = AsyncClient::builder("token".to_string())
+            .async_transport(mock.clone())
+            .retry_policy(RetryPolicy {
+                backoff_base: Duration::ZERO,
+                jitter: Duration::ZERO,
+                ..RetryPolicy::default()
+            })
+            .build_async();
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let branch_detail = client.get_branch_detail(&author, "main").await.unwrap();
+        assert_eq!(branch_detail.name, "main");
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_compare() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let target = compare::CompareTarget::commits("aaa", "bbb");
+        let comparison = client.compare(&author, &target).await.unwrap();
+        assert_eq!(comparison.base_commit, "aaa");
+        assert_eq!(comparison.head_commit, "bbb");
+        assert_eq!(comparison.files[0].missed_changed_lines(), vec![2]);
+
+        let comparison = client
+            .compare(&author, &compare::CompareTarget::pull(12))
+            .await
+            .unwrap();
+        assert_eq!(comparison.head_commit, "bbb");
+    }
+
+    #[tokio::test]
+    async fn test_get_pulls() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let pulls = client
+            .get_pulls(&author, Some(pulls::PullState::Open), &PageOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(pulls.len(), 1);
+        assert_eq!(pulls[0].pullid, 12);
+
+        let pull = client.get_pull(&author, 12).await.unwrap();
+        assert_eq!(pull.title.as_deref(), Some("Add compare endpoint"));
+        assert_eq!(pull.state, pulls::PullState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_get_flags() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let flags = client
+            .get_flags(&author, &PageOptions::new())
+            .await
+            .unwrap();
+        let names: Vec<&str> = flags.iter().map(|flag| flag.flag_name.as_str()).collect();
+        assert_eq!(names, vec!["unit", "integration"]);
+
+        let totals = client.get_flag_totals(&author, "unit").await.unwrap();
+        assert_eq!(totals.coverage, Some(90.0));
+
+        let query = coverage::CoverageQuery::new(coverage::Interval::OneDay).branch("main");
+        let points = client
+            .get_flag_coverage(&author, "unit", &query)
+            .await
+            .unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].avg, 90.0);
+    }
+
+    #[tokio::test]
+    async fn test_get_components() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let components = client.get_components(&author).await.unwrap();
+        let ids: Vec<&str> = components
+            .iter()
+            .map(|component| component.component_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["frontend", "backend"]);
+
+        let report = client
+            .get_component_report(&author, "backend")
+            .await
+            .unwrap();
+        assert_eq!(report.totals.coverage, Some(75.0));
+        assert_eq!(report.files.unwrap()[0].name, "api/server.py");
+
+        let totals = client
+            .get_component_totals(&author, "backend")
+            .await
+            .unwrap();
+        assert_eq!(totals.coverage, Some(75.0));
+    }
+
+    #[tokio::test]
+    async fn test_get_coverage() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let query = coverage::CoverageQuery::new(coverage::Interval::SevenDays)
+            .start_date("2023-07-01")
+            .end_date("2023-08-01")
+            .branch("main")
+            .flag("unit");
+        let points = client.get_coverage(&author, &query).await.unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].timestamp.as_str(), "2023-07-24T00:00:00Z");
+        assert_eq!(points[1].min, 86.05);
+    }
+
+    #[tokio::test]
+    async fn test_get_report() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let query = report::ReportQuery::new()
+            .branch("main")
+            .path("src/payments/");
+        let report = client.get_report(&author, &query).await.unwrap();
+        assert_eq!(report.totals.coverage, Some(66.67));
+        let files = report.files.unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].name, "src/payments/charge.rs");
+        let coverage: Vec<file_report::LineCoverage> = files[0]
+            .line_coverage
+            .iter()
+            .flatten()
+            .map(|line| line.coverage)
+            .collect();
+        assert_eq!(
+            coverage,
+            vec![
+                file_report::LineCoverage::Hit(None),
+                file_report::LineCoverage::Miss
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_report_tree() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let query = report::ReportQuery::new().branch("main").path("src");
+        let tree = client
+            .get_report_tree(&author, &query, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].full_path, "src/payments");
+        assert_eq!(tree[0].coverage, 66.67);
+        assert_eq!(tree[0].children[1].name, "refund.rs");
+    }
+
+    #[tokio::test]
+    async fn test_get_file_report() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let commit = file_report::CommitRef::branch("main");
+        let report = client
+            .get_file_report(&author, "src/payments/charge.rs", &commit)
+            .await
+            .unwrap();
+        assert_eq!(report.name, "src/payments/charge.rs");
+        assert_eq!(
+            report.line_coverage[0].coverage,
+            file_report::LineCoverage::Hit(None)
+        );
+        assert_eq!(report.missed_lines(), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_get_repo() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let repo = client.get_repo(&author).await.unwrap();
+        assert_eq!(repo.repo.name, "codecov-demo");
+        assert_eq!(repo.default_branch(), Some("main"));
+        assert_eq!(repo.latest_coverage(), Some(86.05));
+
+        let config = client.get_repo_config(&author).await.unwrap();
+        assert_eq!(config.upload_token.expose(), "upload-token");
+        assert!(!format!("{:?}", config).contains("upload-token"));
+    }
+
+    #[tokio::test]
+    async fn test_owners_and_users() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let owners = client
+            .list_owners(Service::GitHub, &PageOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(owners.len(), 1);
+        let owner = owners[0].to_owner();
+        assert_eq!(owner.username, "codecov");
+        let detail = client.get_owner(&owner).await.unwrap();
+        assert_eq!(detail.name.as_deref(), Some("Codecov"));
+
+        let query = users::UsersQuery::new().activated(false);
+        let users = client
+            .list_users(&owner, &query, &PageOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+        assert!(!users[0].activated);
+        let user = client.get_user(&owner, "kitsuyui").await.unwrap();
+        assert!(!user.activated);
+
+        let user = client
+            .update_user_activation(&owner, "kitsuyui", true)
+            .await
+            .unwrap();
+        assert!(user.activated);
+        let request = mock.requests().pop().unwrap();
+        assert_eq!(request.method, Method::Patch);
+        assert_eq!(request.body.as_deref(), Some(r#"{"activated":true}"#));
+    }
+
+    #[tokio::test]
+    async fn test_get_commit_uploads() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let uploads = client
+            .get_commit_uploads(
+                &author,
+                "1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+                &PageOptions::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(uploads.len(), 2);
+        assert_eq!(uploads[0].flags, vec!["unit"]);
+        assert!(uploads[1].is_error());
+    }
+
+    #[tokio::test]
+    async fn test_compare_file() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let target = compare::CompareTarget::pull(12);
+        let file = client
+            .compare_file(&author, &target, "src/lib.rs")
+            .await
+            .unwrap();
+        assert_eq!(file.name.head.as_deref(), Some("src/lib.rs"));
+        assert_eq!(file.missed_changed_lines(), vec![2]);
+
+        let segments = client
+            .compare_segments(&author, &target, "src/lib.rs")
+            .await
+            .unwrap();
+        assert_eq!(segments.segments.len(), 1);
+        assert_eq!(segments.missed_changed_lines(), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_compare_breakdowns() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let target = compare::CompareTarget::pull(12);
+        let impacted = client
+            .compare_impacted_files(&author, &target)
+            .await
+            .unwrap();
+        assert_eq!(impacted.files.len(), 1);
+        assert_eq!(impacted.files[0].change_coverage, Some(6.05));
+
+        let flags = client.compare_flags(&author, &target).await.unwrap();
+        assert_eq!(flags[0].name, "unit");
+        assert_eq!(flags[0].totals.coverage_change(), Some(10.0));
+
+        let components = client.compare_components(&author, &target).await.unwrap();
+        assert_eq!(components[0].component_id, "backend");
+        assert_eq!(components[0].totals.diff_coverage(), Some(50.0));
+    }
+
+    #[tokio::test]
+    async fn test_get_test_results() {
+        let mock = Arc::new(fixtures::mock_transport());
+        let client = mock_client(&mock);
+        let author = author::Author::new(Service::GitHub, "codecov", "codecov-demo");
+        let query = test_results::TestResultsQuery::new()
+            .branch("main")
+            .interval(coverage::Interval::SevenDays);
+        let tests = client
+            .get_test_results(&author, &query, &PageOptions::new().page_size(1))
+            .await
+            .unwrap();
+        let names: Vec<&str> = tests.iter().map(|test| test.name.as_str()).collect();
+        assert_eq!(names, vec!["tests::test_parse", "tests::test_ok"]);
+        assert_eq!(tests[0].failure_rate, Some(0.25));
+    }
+}
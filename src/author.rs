@@ -1,31 +1,75 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::errors::Error;
 use crate::owner::Owner;
+use crate::service::{Service, slug_segments};
 
 /**
  * Author is a struct that represents the author of a repo.
  */
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Author {
-    pub service: String,
+    pub service: Service,
     pub username: String,
     pub name: String,
 }
 
 impl Author {
-    pub fn new(service: &str, username: &str, name: &str) -> Author {
+    pub fn new(service: Service, username: &str, name: &str) -> Author {
         Author {
-            service: service.to_string(),
+            service,
             username: username.to_string(),
             name: name.to_string(),
         }
     }
 
     pub fn from_owner(owner: &Owner, name: &str) -> Author {
-        Self::new(&owner.service, &owner.username, name)
+        Self::new(owner.service, &owner.username, name)
     }
 
     pub fn to_owner(&self) -> Owner {
-        Owner::new(&self.service, &self.username)
+        Owner::new(self.service, &self.username)
+    }
+}
+
+/**
+ * Author can be parsed from a slug such as gh/kitsuyui/rust-codecov
+ * or from a Codecov URL such as https://app.codecov.io/gh/kitsuyui/rust-codecov.
+ */
+impl FromStr for Author {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Author, Error> {
+        match slug_segments(s)[..] {
+            [service, username, name, ..] => Ok(Author::new(service.parse()?, username, name)),
+            _ => Err(Error::ParseError(format!(
+                "expected service/owner/repo: {}",
+                s
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let author = "gh/kitsuyui/rust-codecov".parse::<Author>().unwrap();
+        assert_eq!(author.service, Service::GitHub);
+        assert_eq!(author.username, "kitsuyui");
+        assert_eq!(author.name, "rust-codecov");
+
+        let author = "https://app.codecov.io/github/kitsuyui/rust-codecov/tree/main"
+            .parse::<Author>()
+            .unwrap();
+        assert_eq!(author.service, Service::GitHub);
+        assert_eq!(author.name, "rust-codecov");
+
+        assert!("gh/kitsuyui".parse::<Author>().is_err());
+        assert!("githb/kitsuyui/rust-codecov".parse::<Author>().is_err());
     }
 }
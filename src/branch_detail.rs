@@ -1,33 +1,19 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::file_report::Line;
+use crate::service::Service;
+use crate::timestamp::Timestamp;
 use crate::totals::Totals;
 
 /**
- * BranchDetailAPIResponse is an enum wrapping all possible responses from the branches API.
- */
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(untagged)]
-pub enum BranchDetailAPIResponse {
-    Success(Box<BranchDetailAPISuccessResponse>),
-    NotFound(BranchNotFound),
-}
-
-/**
- * BranchesAPIResponse is a struct that represents the response from the branches API.
+ * BranchDetailAPISuccessResponse is a struct that represents the response from the branch detail API.
+ * A missing branch is reported as errors::Error::NotFound by the client.
  */
 #[derive(Serialize, Deserialize, Debug)]
 pub struct BranchDetailAPISuccessResponse {
     pub head_commit: HeadCommit,
     pub name: String,
-    pub updatestamp: String, // TODO: ISO Date
-}
-
-/**
- * BranchNotFound is a struct that represents a branch not found error.
- */
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BranchNotFound {
-    pub detail: String,
+    pub updatestamp: Timestamp,
 }
 
 /**
@@ -43,17 +29,34 @@ pub struct HeadCommit {
     pub parent: Option<String>,
     pub report: Report,
     pub state: Option<String>,
-    pub timestamp: Option<String>, // TODO: ISO Date
+    pub timestamp: Option<Timestamp>,
     pub totals: Totals,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/**
+ * MayBeAuthor is an Author, or Empty when the API returns no author. e.g. {}
+ * An author with a service is always parsed as Author, so an unknown service is an error.
+ */
+#[derive(Serialize, Debug)]
 #[serde(untagged)]
 pub enum MayBeAuthor {
     Author(Author),
     Empty(serde_json::Value),
 }
 
+impl<'de> Deserialize<'de> for MayBeAuthor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MayBeAuthor, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if value.get("service").is_none() {
+            return Ok(MayBeAuthor::Empty(value));
+        }
+        match serde_json::from_value::<Author>(value) {
+            Ok(author) => Ok(MayBeAuthor::Author(author)),
+            Err(e) => Err(serde::de::Error::custom(e)),
+        }
+    }
+}
+
 /**
  * Author is a struct that represents an author.
  * Note: username is an optional field.
@@ -62,39 +65,33 @@ pub enum MayBeAuthor {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Author {
     pub name: String,
-    pub service: String,
+    pub service: Service,
     pub username: Option<String>,
 }
 
 /**
  * Report is a struct that represents a report.
+ * commit_file_url is only returned by the report API.
  */
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Report {
     #[serde(default)]
     pub files: Option<Vec<File>>,
     pub totals: Totals,
+    #[serde(default)]
+    pub commit_file_url: Option<String>,
 }
 
 /**
  * File is a struct that represents a file.
+ * line_coverage is only returned by the commit detail and report APIs.
  */
 #[derive(Serialize, Deserialize, Debug)]
 pub struct File {
     pub name: String,
     pub totals: Totals,
-}
-
-impl BranchDetailAPIResponse {
-    /**
-     * Returns the latest coverage for a branch.
-     */
-    pub fn latest_coverage(&self) -> f64 {
-        match self {
-            BranchDetailAPIResponse::Success(branch_detail) => branch_detail.latest_coverage(),
-            BranchDetailAPIResponse::NotFound(_) => 0.0,
-        }
-    }
+    #[serde(default)]
+    pub line_coverage: Option<Vec<Line>>,
 }
 
 impl BranchDetailAPISuccessResponse {
@@ -166,7 +163,7 @@ mod tests {
             "name": "main",
             "updatestamp": "2023-08-01T19:10:56.045522Z"
         });
-        serde_json::from_value::<BranchDetailAPIResponse>(j).unwrap();
+        serde_json::from_value::<BranchDetailAPISuccessResponse>(j).unwrap();
     }
 
     #[test]
@@ -267,7 +264,7 @@ mod tests {
             "name": "main",
             "updatestamp": "2023-08-01T19:10:56.045522Z"
         });
-        serde_json::from_value::<BranchDetailAPIResponse>(j).unwrap();
+        serde_json::from_value::<BranchDetailAPISuccessResponse>(j).unwrap();
     }
 
     #[test]
@@ -314,9 +311,33 @@ mod tests {
             "name": "main",
             "updatestamp": "2022-02-15T19:25:13.075533Z"
         });
-        let response = serde_json::from_value::<BranchDetailAPIResponse>(j).unwrap();
-        if let BranchDetailAPIResponse::Success(branch_detail) = response {
-            assert_eq!(branch_detail.head_commit.parent, Some(String::from("")));
+        let branch_detail = serde_json::from_value::<BranchDetailAPISuccessResponse>(j).unwrap();
+        assert_eq!(branch_detail.head_commit.parent, Some(String::from("")));
+    }
+
+    #[test]
+    fn test_deserialize_author() {
+        let author = serde_json::from_value::<MayBeAuthor>(json!({
+            "name": "renovate[bot]",
+            "service": "github",
+            "username": null
+        }))
+        .unwrap();
+        match author {
+            MayBeAuthor::Author(author) => assert_eq!(author.service, Service::GitHub),
+            MayBeAuthor::Empty(_) => panic!("expected an author"),
         }
+        assert!(matches!(
+            serde_json::from_value::<MayBeAuthor>(json!({})).unwrap(),
+            MayBeAuthor::Empty(_)
+        ));
+        assert!(
+            serde_json::from_value::<MayBeAuthor>(json!({
+                "name": "renovate[bot]",
+                "service": "githb",
+                "username": null
+            }))
+            .is_err()
+        );
     }
 }
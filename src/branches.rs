@@ -1,18 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::url::Url;
+use crate::pagination::Paginated;
+use crate::timestamp::Timestamp;
 
 /**
- * BranchesAPIResponse is a struct that represents the response from the branches API.
+ * BranchesAPIResponse is a page of branches returned by the branches API.
  */
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BranchesAPIResponse {
-    pub results: Vec<Branch>,
-    pub count: usize,
-    pub next: Option<Url>,
-    pub previous: Option<Url>,
-    pub total_pages: usize,
-}
+pub type BranchesAPIResponse = Paginated<Branch>;
 
 /**
  * Branch is a struct that represents a branch.
@@ -20,5 +14,5 @@ pub struct BranchesAPIResponse {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Branch {
     pub name: String,
-    pub updatestamp: String, // TODO: ISO Date
+    pub updatestamp: Timestamp,
 }
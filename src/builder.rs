@@ -0,0 +1,190 @@
+#[cfg(feature = "async")]
+use crate::async_client::AsyncClient;
+use crate::author::Author;
+#[cfg(feature = "blocking")]
+use crate::client::Client;
+use crate::errors::Error;
+use crate::owner::Owner;
+use crate::retry::RetryPolicy;
+use crate::service::Service;
+#[cfg(feature = "async")]
+use crate::transport::AsyncHttpTransport;
+#[cfg(feature = "blocking")]
+use crate::transport::HttpTransport;
+
+/**
+ * DEFAULT_API_URL is the base URL of the hosted Codecov API (v2).
+ */
+pub const DEFAULT_API_URL: &str = "https://codecov.io/api/v2";
+
+/**
+ * ClientBuilder is a builder for Client and AsyncClient.
+ * It allows to point the client at a self-hosted Codecov instance.
+ */
+pub struct ClientBuilder {
+    pub(crate) token: String,
+    pub(crate) base_url: String,
+    pub(crate) retry_policy: RetryPolicy,
+    #[cfg(feature = "blocking")]
+    pub(crate) transport: Option<Box<dyn HttpTransport>>,
+    #[cfg(feature = "async")]
+    pub(crate) async_transport: Option<Box<dyn AsyncHttpTransport>>,
+}
+
+impl ClientBuilder {
+    pub fn new(token: String) -> ClientBuilder {
+        ClientBuilder {
+            token,
+            base_url: DEFAULT_API_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            #[cfg(feature = "blocking")]
+            transport: None,
+            #[cfg(feature = "async")]
+            async_transport: None,
+        }
+    }
+
+    /**
+     * from_env reads CODECOV_OWNER_TOKEN from environment variable.
+     * If CODECOV_API_URL is set, it is used as the base URL of the API.
+     */
+    pub fn from_env() -> Result<ClientBuilder, Error> {
+        let token = match std::env::var("CODECOV_OWNER_TOKEN") {
+            Ok(token) => token,
+            Err(e) => return Err(Error::EnvError(e)),
+        };
+        let mut builder = ClientBuilder::new(token);
+        match std::env::var("CODECOV_API_URL") {
+            Ok(base_url) => builder = builder.base_url(&base_url),
+            Err(std::env::VarError::NotPresent) => {}
+            Err(e) => return Err(Error::EnvError(e)),
+        }
+        Ok(builder)
+    }
+
+    /**
+     * base_url sets the base URL of the API. e.g. https://codecov.example.com/api/v2
+     * A trailing slash is ignored.
+     */
+    pub fn base_url(mut self, base_url: &str) -> ClientBuilder {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /**
+     * retry_policy sets how failed requests are retried. RetryPolicy::default() is used if not set.
+     * Use RetryPolicy::none() to disable retries.
+     */
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> ClientBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /**
+     * transport sets the HTTP transport of the blocking Client. ReqwestTransport is used if not set.
+     * e.g. MockTransport for offline tests.
+     * It is only used by build. build_async uses async_transport instead.
+     */
+    #[cfg(feature = "blocking")]
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> ClientBuilder {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    /**
+     * async_transport sets the HTTP transport of AsyncClient. AsyncReqwestTransport is used if not set.
+     * It is only used by build_async. build uses transport instead.
+     */
+    #[cfg(feature = "async")]
+    pub fn async_transport(
+        mut self,
+        transport: impl AsyncHttpTransport + 'static,
+    ) -> ClientBuilder {
+        self.async_transport = Some(Box::new(transport));
+        self
+    }
+
+    /**
+     * build returns a blocking Client.
+     */
+    #[cfg(feature = "blocking")]
+    pub fn build(self) -> Client {
+        Client::from_builder(self)
+    }
+
+    /**
+     * build_async returns an AsyncClient.
+     */
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> AsyncClient {
+        AsyncClient::from_builder(self)
+    }
+}
+
+/**
+ * Endpoints builds API URLs from the base URL.
+ * It is shared by Client and AsyncClient.
+ */
+pub(crate) struct Endpoints {
+    base_url: String,
+}
+
+impl Endpoints {
+    pub(crate) fn new(base_url: String) -> Endpoints {
+        Endpoints { base_url }
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn service_endpoint(&self, service: Service) -> String {
+        format!("{}/{}", self.base_url, service)
+    }
+
+    pub(crate) fn owner_endpoint(&self, owner: &Owner) -> String {
+        format!(
+            "{}/{}",
+            self.service_endpoint(owner.service),
+            owner.username
+        )
+    }
+
+    pub(crate) fn repos_endpoint(&self, author: &Author) -> String {
+        let owner_endpoint = self.owner_endpoint(&author.to_owner());
+        format!("{}/repos/{}", owner_endpoint, author.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url() {
+        let builder = ClientBuilder::new("token".to_string());
+        assert_eq!(builder.base_url, DEFAULT_API_URL);
+
+        let builder = builder.base_url("https://codecov.example.com/api/v2/");
+        assert_eq!(builder.base_url, "https://codecov.example.com/api/v2");
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let endpoints = Endpoints::new("https://codecov.example.com/api/v2".to_string());
+        assert_eq!(
+            endpoints.service_endpoint(Service::GitHub),
+            "https://codecov.example.com/api/v2/github"
+        );
+        let owner = Owner::new(Service::GitHub, "kitsuyui");
+        assert_eq!(
+            endpoints.owner_endpoint(&owner),
+            "https://codecov.example.com/api/v2/github/kitsuyui"
+        );
+        let author = owner.new_author("rust-codecov");
+        assert_eq!(
+            endpoints.repos_endpoint(&author),
+            "https://codecov.example.com/api/v2/github/kitsuyui/repos/rust-codecov"
+        );
+    }
+}
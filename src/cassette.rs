@@ -0,0 +1,394 @@
+/**
+ * Record/replay of API responses.
+ * RecordingTransport wraps a real transport and records each request/response into a Cassette.
+ * A saved Cassette can be replayed with Cassette::replay, which never touches the network.
+ */
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+#[cfg(feature = "async")]
+use crate::transport::{AsyncHttpTransport, BoxFuture};
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, Method, MockTransport};
+
+/**
+ * Headers whose values are replaced by SCRUBBED before they are recorded.
+ */
+const SENSITIVE_HEADERS: [&str; 3] = ["authorization", "cookie", "set-cookie"];
+/**
+ * JSON fields whose values are replaced by SCRUBBED in recorded bodies. e.g. the repo config API
+ */
+const SENSITIVE_FIELDS: [&str; 2] = ["upload_token", "graph_token"];
+const SCRUBBED: &str = "[SCRUBBED]";
+
+/**
+ * Cassette is a list of recorded interactions.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+/**
+ * Interaction is a single recorded request and its response.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub url: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<RecordedBody>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    pub body: RecordedBody,
+}
+
+/**
+ * RecordedBody keeps a JSON body as JSON so that cassettes are easy to read and edit.
+ * A body that is not JSON is kept as text. e.g. {"json": {...}} or {"text": "Bad Gateway"}
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordedBody {
+    Json(serde_json::Value),
+    Text(String),
+}
+
+impl RecordedBody {
+    /**
+     * new parses body as JSON, scrubbing token fields, or keeps it as text.
+     */
+    pub fn new(body: &str) -> RecordedBody {
+        match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(mut value) => {
+                scrub_fields(&mut value);
+                RecordedBody::Json(value)
+            }
+            Err(_) => RecordedBody::Text(body.to_string()),
+        }
+    }
+
+    /**
+     * to_body returns the body as it is sent over the wire.
+     */
+    pub fn to_body(&self) -> String {
+        match self {
+            RecordedBody::Json(value) => value.to_string(),
+            RecordedBody::Text(text) => text.to_string(),
+        }
+    }
+}
+
+impl Cassette {
+    pub fn new() -> Cassette {
+        Cassette::default()
+    }
+
+    /**
+     * load reads a cassette from a JSON file.
+     */
+    pub fn load(path: impl AsRef<Path>) -> Result<Cassette, Error> {
+        let content = std::fs::read_to_string(path)?;
+        Cassette::from_json(&content)
+    }
+
+    pub fn from_json(content: &str) -> Result<Cassette, Error> {
+        match serde_json::from_str::<Cassette>(content) {
+            Ok(cassette) => Ok(cassette),
+            Err(e) => Err(Error::DeserializeError(e)),
+        }
+    }
+
+    /**
+     * save writes the cassette to a JSON file.
+     */
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content + "\n")?;
+        Ok(())
+    }
+
+    /**
+     * record adds an interaction. Sensitive headers such as the bearer token
+     * and token fields of JSON bodies are scrubbed.
+     */
+    pub fn record(&mut self, request: &HttpRequest, response: &HttpResponse) {
+        self.interactions.push(Interaction {
+            request: RecordedRequest {
+                method: request.method,
+                url: request.url.to_string(),
+                headers: scrub_headers(&request.headers),
+                body: request.body.as_deref().map(RecordedBody::new),
+            },
+            response: RecordedResponse {
+                status: response.status,
+                headers: scrub_headers(&response.headers),
+                body: RecordedBody::new(&response.body),
+            },
+        });
+    }
+
+    /**
+     * replay returns a transport serving the recorded responses.
+     * Responses for the same method + URL are returned in the recorded order.
+     */
+    pub fn replay(&self) -> MockTransport {
+        let mock = MockTransport::new();
+        for interaction in &self.interactions {
+            let response = &interaction.response;
+            let mut http_response = HttpResponse::new(response.status, &response.body.to_body());
+            for (name, value) in &response.headers {
+                http_response = http_response.header(name, value);
+            }
+            mock.push_response(
+                interaction.request.method,
+                &interaction.request.url,
+                http_response,
+            );
+        }
+        mock
+    }
+}
+
+fn scrub_headers(headers: &[(String, String)]) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.to_ascii_lowercase();
+            if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                (name, SCRUBBED.to_string())
+            } else {
+                (name, value.to_string())
+            }
+        })
+        .collect()
+}
+
+fn scrub_fields(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, value) in map.iter_mut() {
+                if SENSITIVE_FIELDS.contains(&name.as_str()) && !value.is_null() {
+                    *value = serde_json::Value::String(SCRUBBED.to_string());
+                } else {
+                    scrub_fields(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(scrub_fields),
+        _ => {}
+    }
+}
+
+/**
+ * RecordingTransport sends requests with the inner transport and records them.
+ * Call save (or cassette) after the requests are made.
+ */
+pub struct RecordingTransport<T> {
+    inner: T,
+    cassette: Mutex<Cassette>,
+}
+
+impl<T> RecordingTransport<T> {
+    pub fn new(inner: T) -> RecordingTransport<T> {
+        RecordingTransport {
+            inner,
+            cassette: Mutex::new(Cassette::new()),
+        }
+    }
+
+    /**
+     * cassette returns a copy of the interactions recorded so far.
+     */
+    pub fn cassette(&self) -> Cassette {
+        match self.cassette.lock() {
+            Ok(cassette) => cassette.clone(),
+            Err(_) => Cassette::new(),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        self.cassette().save(path)
+    }
+
+    fn record(&self, request: &HttpRequest, response: &HttpResponse) {
+        if let Ok(mut cassette) = self.cassette.lock() {
+            cassette.record(request, response);
+        }
+    }
+}
+
+impl<T: HttpTransport> HttpTransport for RecordingTransport<T> {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        let response = self.inner.send(request)?;
+        self.record(request, &response);
+        Ok(response)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncHttpTransport> AsyncHttpTransport for RecordingTransport<T> {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        Box::pin(async move {
+            let response = self.inner.send(request).await?;
+            self.record(request, &response);
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "blocking")]
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[cfg(feature = "blocking")]
+    fn cassette_path(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("cassettes")
+            .join(name)
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let url = fixtures::url("/github/codecov/repos/codecov-demo/branches/main");
+        let recorder = RecordingTransport::new(fixtures::mock_transport());
+        let request = HttpRequest::get(&url).header("Authorization", "bearer secret-token");
+        let response = HttpTransport::send(&recorder, &request).unwrap();
+
+        let cassette = recorder.cassette();
+        assert_eq!(cassette.interactions.len(), 1);
+        let interaction = &cassette.interactions[0];
+        assert_eq!(interaction.request.url, url);
+        assert_eq!(
+            interaction.request.headers.get("authorization"),
+            Some(&SCRUBBED.to_string())
+        );
+        assert_eq!(
+            interaction.response.body,
+            RecordedBody::Json(fixtures::branch_detail("main"))
+        );
+        let content = serde_json::to_string(&cassette).unwrap();
+        assert!(!content.contains("secret-token"));
+
+        let cassette = Cassette::from_json(&content).unwrap();
+        let replayed = HttpTransport::send(&cassette.replay(), &request).unwrap();
+        assert_eq!(replayed.status, response.status);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&replayed.body).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&response.body).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_scrub_token_fields() {
+        let url = fixtures::url("/github/codecov/repos/codecov-demo/config");
+        let recorder = RecordingTransport::new(fixtures::mock_transport());
+        HttpTransport::send(&recorder, &HttpRequest::get(&url)).unwrap();
+
+        let cassette = recorder.cassette();
+        assert_eq!(
+            cassette.interactions[0].response.body,
+            RecordedBody::Json(json!({"upload_token": SCRUBBED, "graph_token": SCRUBBED}))
+        );
+        let content = serde_json::to_string(&cassette).unwrap();
+        assert!(!content.contains("upload-token"));
+        assert!(!content.contains("graph-token"));
+    }
+
+    #[test]
+    fn test_non_json_body() {
+        let mut cassette = Cassette::new();
+        let request = HttpRequest::get("https://codecov.io/api/v2/github/codecov");
+        cassette.record(&request, &HttpResponse::new(502, "Bad Gateway"));
+        assert_eq!(
+            cassette.interactions[0].response.body,
+            RecordedBody::Text("Bad Gateway".to_string())
+        );
+        let content = serde_json::to_string(&cassette).unwrap();
+        assert!(content.contains(r#""body":{"text":"Bad Gateway"}"#));
+        let cassette = Cassette::from_json(&content).unwrap();
+        let response = HttpTransport::send(&cassette.replay(), &request).unwrap();
+        assert_eq!(response.body, "Bad Gateway");
+    }
+
+    #[test]
+    fn test_json_string_body() {
+        let mut cassette = Cassette::new();
+        let request = HttpRequest::get("https://codecov.io/api/v2/github/codecov");
+        cassette.record(&request, &HttpResponse::new(200, r#""ok""#));
+        assert_eq!(
+            cassette.interactions[0].response.body,
+            RecordedBody::Json(json!("ok"))
+        );
+        let content = serde_json::to_string(&cassette).unwrap();
+        let cassette = Cassette::from_json(&content).unwrap();
+        let response = HttpTransport::send(&cassette.replay(), &request).unwrap();
+        assert_eq!(response.body, r#""ok""#);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_replay_cassette_file() {
+        let cassette = Cassette::load(cassette_path("codecov-demo.json")).unwrap();
+        let client = crate::Client::builder("token".to_string())
+            .transport(Arc::new(cassette.replay()))
+            .build();
+
+        let owner = crate::owner::Owner::new(crate::service::Service::GitHub, "codecov");
+        let repos = client.get_all_repos(&owner).unwrap();
+        assert_eq!(repos.len(), 3);
+
+        let author = owner.new_author("codecov-demo");
+        let branch_detail = client.get_branch_detail(&author, "main").unwrap();
+        assert_eq!(branch_detail.name, "main");
+        assert_eq!(branch_detail.latest_coverage(), 86.05);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_save_cassette_file() {
+        let recorder = Arc::new(RecordingTransport::new(fixtures::mock_transport()));
+        let client = crate::Client::builder("secret-token".to_string())
+            .transport(recorder.clone())
+            .build();
+        let owner = crate::owner::Owner::new(crate::service::Service::GitHub, "codecov");
+        client.get_all_repos(&owner).unwrap();
+        client
+            .get_branch_detail(&owner.new_author("codecov-demo"), "main")
+            .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("codecov-cassette-{}.json", std::process::id()));
+        recorder.save(&path).unwrap();
+        let saved = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(saved, recorder.cassette());
+        assert_eq!(
+            saved,
+            Cassette::load(cassette_path("codecov-demo.json")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,143 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/commits/{commitid} endpoint returns a single commit with its full report.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::branch_detail::{MayBeAuthor, Report};
+use crate::timestamp::Timestamp;
+use crate::totals::Totals;
+
+/**
+ * CommitDetail is a struct that represents the response from the commit detail API.
+ * Unlike commits::Commit, the report includes per-file totals and line coverage.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CommitDetail {
+    pub commitid: String,
+    pub message: Option<String>,
+    pub timestamp: Option<Timestamp>,
+    pub ci_passed: Option<bool>,
+    pub author: Option<MayBeAuthor>,
+    pub branch: Option<String>,
+    pub totals: Totals,
+    pub state: Option<String>,
+    pub parent: Option<String>,
+    pub report: Report,
+}
+
+impl CommitDetail {
+    /**
+     * Returns the coverage of the commit.
+     */
+    pub fn coverage(&self) -> f64 {
+        self.totals.coverage.unwrap_or(0.0)
+    }
+
+    /**
+     * Returns the files in the report. Empty if the report has no files.
+     */
+    pub fn files(&self) -> &[crate::branch_detail::File] {
+        match &self.report.files {
+            Some(files) => files,
+            None => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::file_report::LineCoverage;
+
+    #[test]
+    fn test_deserialize_commit_detail() {
+        let j = json!({
+            "commitid": "1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+            "message": "chore(deps): update dependency @swc/core to v1.3.73",
+            "timestamp": "2023-08-01T15:41:47Z",
+            "ci_passed": true,
+            "author": {
+                "name": "renovate[bot]",
+                "service": "github",
+                "username": null
+            },
+            "branch": "main",
+            "totals": {
+                "files": 1,
+                "lines": 4,
+                "hits": 2,
+                "misses": 1,
+                "partials": 1,
+                "coverage": 50.0,
+                "branches": 2,
+                "methods": 0,
+                "sessions": 1,
+                "complexity": 0.0,
+                "complexity_total": 0.0,
+                "complexity_ratio": 0,
+                "diff": 0
+            },
+            "state": "complete",
+            "parent": "5a4b2987ca3a8a7b54efac914fd72455ebff50ba",
+            "report": {
+                "totals": {
+                    "files": 1,
+                    "lines": 4,
+                    "hits": 2,
+                    "misses": 1,
+                    "partials": 1,
+                    "coverage": 50.0,
+                    "branches": 2,
+                    "methods": 0,
+                    "messages": 0,
+                    "sessions": 1,
+                    "complexity": 0.0,
+                    "complexity_total": 0.0,
+                    "complexity_ratio": 0,
+                    "diff": null
+                },
+                "files": [
+                    {
+                        "name": "src/lib.rs",
+                        "totals": {
+                            "files": 0,
+                            "lines": 4,
+                            "hits": 2,
+                            "misses": 1,
+                            "partials": 1,
+                            "coverage": 50.0,
+                            "branches": 2,
+                            "methods": 0,
+                            "messages": 0,
+                            "sessions": 0,
+                            "complexity": 0.0,
+                            "complexity_total": 0.0,
+                            "complexity_ratio": 0,
+                            "diff": null
+                        },
+                        "line_coverage": [[1, 0], [2, 0], [3, 1], [5, 2]]
+                    }
+                ]
+            }
+        });
+        let commit = serde_json::from_value::<CommitDetail>(j).unwrap();
+        assert_eq!(commit.coverage(), 50.0);
+        let files = commit.files();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "src/lib.rs");
+        let lines = files[0].line_coverage.as_ref().unwrap();
+        let numbers: Vec<usize> = lines.iter().map(|line| line.number).collect();
+        assert_eq!(numbers, vec![1, 2, 3, 5]);
+        assert_eq!(lines[2].coverage, LineCoverage::Miss);
+        assert_eq!(
+            lines[3].coverage,
+            LineCoverage::Partial {
+                covered: None,
+                total: None
+            }
+        );
+    }
+}
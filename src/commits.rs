@@ -4,19 +4,16 @@
  */
 use serde::{Deserialize, Serialize};
 
-use crate::url::Url;
+use crate::pagination::Paginated;
+use crate::service::Service;
+use crate::timestamp::Timestamp;
+// commits::Totals still resolves to totals::Totals, but its diff field is now Option<totals::Diff>.
+pub use crate::totals::Totals;
 
 /**
- * CommitsAPIResponse is a struct that represents the response from the commits API.
+ * CommitsAPIResponse is a page of commits returned by the commits API.
  */
-#[derive(Serialize, Deserialize, Debug)]
-pub struct CommitsAPIResponse {
-    pub results: Vec<Commit>,
-    pub count: usize,
-    pub next: Option<Url>,
-    pub previous: Option<Url>,
-    pub total_pages: usize,
-}
+pub type CommitsAPIResponse = Paginated<Commit>;
 
 /**
  * CommitAuthor is a struct that represents the author of a commit.
@@ -25,7 +22,7 @@ pub struct CommitsAPIResponse {
  */
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CommitAuthor {
-    pub service: String,
+    pub service: Service,
     pub username: String,
     pub name: Option<String>,
 }
@@ -37,7 +34,7 @@ pub struct CommitAuthor {
 pub struct Commit {
     pub commitid: String,
     pub message: Option<String>,
-    pub timestamp: Option<String>, // TODO: ISO Date
+    pub timestamp: Option<Timestamp>,
     pub ci_passed: bool,
     pub author: Option<CommitAuthor>,
     pub branch: Option<String>,
@@ -46,29 +43,11 @@ pub struct Commit {
     pub parent: Option<String>,
 }
 
-/**
- * Totals is a struct that represents the totals for a commit.
- */
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Totals {
-    pub files: Option<usize>,
-    pub lines: Option<usize>,
-    pub hits: Option<usize>,
-    pub misses: Option<usize>,
-    pub partials: Option<usize>,
-    #[serde(default)]
-    pub coverage: Option<f64>,
-    pub branches: Option<usize>,
-    pub methods: Option<usize>,
-    pub sessions: Option<usize>,
-    pub complexity: Option<f64>,
-    pub complexity_total: Option<f64>,
-    #[serde(default)]
-    pub complexity_ratio: Option<f64>,
-    pub diff: Option<usize>,
-}
-
 impl CommitsAPIResponse {
+    /**
+     * coverage returns the unweighted average coverage of the commits in this page.
+     * Use Client::get_coverage for a coverage trend over time.
+     */
     pub fn coverage(&self) -> Option<f64> {
         if self.count == 0 {
             return None;
@@ -86,6 +65,7 @@ mod tests {
     #[test]
     fn test_coverage() {
         use super::*;
+        use crate::totals::Diff;
         let mut response = CommitsAPIResponse {
             results: vec![],
             count: 1,
@@ -97,10 +77,10 @@ mod tests {
         let commit = Commit {
             commitid: String::from("123"),
             message: Some(String::from("message")),
-            timestamp: Some(String::from("timestamp")),
+            timestamp: Some("2023-08-01T15:41:47Z".parse().unwrap()),
             ci_passed: true,
             author: Some(CommitAuthor {
-                service: String::from("service"),
+                service: Service::GitHub,
                 username: String::from("username"),
                 name: Some(String::from("name")),
             }),
@@ -118,7 +98,7 @@ mod tests {
                 complexity: Some(1.0),
                 complexity_total: Some(1.0),
                 complexity_ratio: Some(1.0),
-                diff: Some(1),
+                diff: Some(Diff::Value(1)),
             },
             state: Some(String::from("state")),
             parent: Some(String::from("parent")),
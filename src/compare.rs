@@ -0,0 +1,554 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/compare endpoint returns the coverage difference between two commits or of a pull request.
+ * /repos/{repo}/compare/file/{path} and /repos/{repo}/compare/segments/{path} return it for a single file.
+ * /repos/{repo}/compare/impacted_files, /flags and /components break it down per file, flag and component.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::file_report::LineCoverage;
+use crate::totals::Totals;
+
+/**
+ * CompareTarget selects what is compared: two commits or a pull request.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompareTarget {
+    Commits { base: String, head: String },
+    Pull(u64),
+}
+
+impl CompareTarget {
+    pub fn commits(base: &str, head: &str) -> CompareTarget {
+        CompareTarget::Commits {
+            base: base.to_string(),
+            head: head.to_string(),
+        }
+    }
+
+    pub fn pull(pullid: u64) -> CompareTarget {
+        CompareTarget::Pull(pullid)
+    }
+
+    /**
+     * query returns the query parameters of the compare endpoints.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn query(&self) -> Vec<(&'static str, Option<String>)> {
+        match self {
+            CompareTarget::Commits { base, head } => vec![
+                ("base", Some(base.to_string())),
+                ("head", Some(head.to_string())),
+            ],
+            CompareTarget::Pull(pullid) => vec![("pullid", Some(pullid.to_string()))],
+        }
+    }
+}
+
+/**
+ * Comparison is a struct that represents the response from the compare API.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Comparison {
+    pub base_commit: String,
+    pub head_commit: String,
+    pub totals: ComparisonTotals,
+    #[serde(default)]
+    pub files: Vec<ComparisonFile>,
+    #[serde(default)]
+    pub untracked: Vec<String>,
+    #[serde(default)]
+    pub has_unmerged_base_commits: bool,
+}
+
+/**
+ * ComparisonTotals is a struct that represents the totals of the base and head commits.
+ * patch is the coverage of the changed lines.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ComparisonTotals {
+    pub base: Option<Totals>,
+    pub head: Option<Totals>,
+    pub patch: Option<Totals>,
+}
+
+/**
+ * ComparisonFile is a struct that represents the change of a file.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ComparisonFile {
+    pub name: FileName,
+    pub totals: ComparisonTotals,
+    #[serde(default)]
+    pub has_diff: bool,
+    pub stats: Option<LineStats>,
+    #[serde(default)]
+    pub lines: Vec<ComparisonLine>,
+}
+
+/**
+ * ImpactedFiles is a struct that represents the response from the compare impacted files API.
+ * state is processed once the comparison is computed.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImpactedFiles {
+    #[serde(default)]
+    pub files: Vec<ImpactedFile>,
+    pub state: Option<String>,
+}
+
+/**
+ * ImpactedFile is a file whose coverage changed between the base and head commits.
+ * change_coverage is head coverage - base coverage.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImpactedFile {
+    pub file_name: Option<String>,
+    pub base_name: Option<String>,
+    pub head_name: Option<String>,
+    pub base_coverage: Option<Totals>,
+    pub head_coverage: Option<Totals>,
+    pub patch_coverage: Option<Totals>,
+    pub change_coverage: Option<f64>,
+    #[serde(default)]
+    pub file_was_added_by_diff: bool,
+    #[serde(default)]
+    pub file_was_removed_by_diff: bool,
+}
+
+/**
+ * FlagComparison is the coverage change of a flag returned by the compare flags API.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FlagComparison {
+    pub name: String,
+    #[serde(flatten)]
+    pub totals: ReportTotalsChange,
+}
+
+/**
+ * ComponentComparison is the coverage change of a component returned by the compare components API.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ComponentComparison {
+    pub component_id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub totals: ReportTotalsChange,
+}
+
+/**
+ * ReportTotalsChange holds the base, head and diff totals of a flag or a component.
+ * diff_totals is the totals of the changed lines.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReportTotalsChange {
+    pub base_report_totals: Option<Totals>,
+    pub head_report_totals: Option<Totals>,
+    pub diff_totals: Option<Totals>,
+}
+
+/**
+ * FileSegments is a struct that represents the response from the compare segments API.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FileSegments {
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+}
+
+/**
+ * Segment is a diff hunk of a file with the base and head coverage of its lines.
+ * header is the hunk header. e.g. -1,2 +1,3
+ * has_unintended_changes is true when the coverage changed on lines outside of the diff.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Segment {
+    pub header: Option<String>,
+    #[serde(default)]
+    pub has_unintended_changes: bool,
+    #[serde(default)]
+    pub lines: Vec<ComparisonLine>,
+}
+
+/**
+ * FileName is the name of a file in the base and head commits.
+ * base is None for an added file, head is None for a removed file.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FileName {
+    pub base: Option<String>,
+    pub head: Option<String>,
+}
+
+/**
+ * LineStats is the number of lines added and removed in a file.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LineStats {
+    pub added: usize,
+    pub removed: usize,
+}
+
+/**
+ * ComparisonLine is a struct that represents a line of a changed file.
+ * coverage is the coverage type where 0 = hit, 1 = miss, 2 = partial, -1 = skipped, null = ignored.
+ * Use base_coverage and head_coverage to get it as a LineCoverage.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ComparisonLine {
+    pub value: Option<String>,
+    pub number: LineNumber,
+    pub coverage: LineCoverageChange,
+    #[serde(default)]
+    pub is_diff: bool,
+    #[serde(default)]
+    pub added: bool,
+    #[serde(default)]
+    pub removed: bool,
+    pub sessions: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LineNumber {
+    pub base: Option<usize>,
+    pub head: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LineCoverageChange {
+    pub base: Option<i64>,
+    pub head: Option<i64>,
+}
+
+fn coverage_of(totals: &Option<Totals>) -> Option<f64> {
+    totals.as_ref().and_then(|totals| totals.coverage)
+}
+
+impl ComparisonTotals {
+    pub fn base_coverage(&self) -> Option<f64> {
+        coverage_of(&self.base)
+    }
+
+    pub fn head_coverage(&self) -> Option<f64> {
+        coverage_of(&self.head)
+    }
+
+    pub fn patch_coverage(&self) -> Option<f64> {
+        coverage_of(&self.patch)
+    }
+
+    /**
+     * coverage_change returns head coverage - base coverage.
+     */
+    pub fn coverage_change(&self) -> Option<f64> {
+        Some(self.head_coverage()? - self.base_coverage()?)
+    }
+}
+
+impl ReportTotalsChange {
+    pub fn base_coverage(&self) -> Option<f64> {
+        coverage_of(&self.base_report_totals)
+    }
+
+    pub fn head_coverage(&self) -> Option<f64> {
+        coverage_of(&self.head_report_totals)
+    }
+
+    pub fn diff_coverage(&self) -> Option<f64> {
+        coverage_of(&self.diff_totals)
+    }
+
+    /**
+     * coverage_change returns head coverage - base coverage.
+     */
+    pub fn coverage_change(&self) -> Option<f64> {
+        Some(self.head_coverage()? - self.base_coverage()?)
+    }
+}
+
+impl Comparison {
+    /**
+     * coverage_change returns head coverage - base coverage of the whole report.
+     */
+    pub fn coverage_change(&self) -> Option<f64> {
+        self.totals.coverage_change()
+    }
+
+    /**
+     * changed_files returns the files which have a diff.
+     */
+    pub fn changed_files(&self) -> impl Iterator<Item = &ComparisonFile> {
+        self.files.iter().filter(|file| file.has_diff)
+    }
+}
+
+impl ComparisonFile {
+    /**
+     * coverage_before returns the coverage of the file in the base commit.
+     */
+    pub fn coverage_before(&self) -> Option<f64> {
+        self.totals.base_coverage()
+    }
+
+    /**
+     * coverage_after returns the coverage of the file in the head commit.
+     */
+    pub fn coverage_after(&self) -> Option<f64> {
+        self.totals.head_coverage()
+    }
+
+    pub fn lines_added(&self) -> usize {
+        self.stats.as_ref().map_or(0, |stats| stats.added)
+    }
+
+    pub fn lines_removed(&self) -> usize {
+        self.stats.as_ref().map_or(0, |stats| stats.removed)
+    }
+
+    /**
+     * missed_changed_lines returns the head line numbers of added lines which are not covered.
+     */
+    pub fn missed_changed_lines(&self) -> Vec<usize> {
+        missed_changed_lines(&self.lines)
+    }
+}
+
+impl Segment {
+    /**
+     * missed_changed_lines returns the head line numbers of added lines which are not covered.
+     */
+    pub fn missed_changed_lines(&self) -> Vec<usize> {
+        missed_changed_lines(&self.lines)
+    }
+}
+
+impl FileSegments {
+    /**
+     * missed_changed_lines returns the head line numbers of added lines which are not covered.
+     */
+    pub fn missed_changed_lines(&self) -> Vec<usize> {
+        self.segments
+            .iter()
+            .flat_map(|segment| segment.missed_changed_lines())
+            .collect()
+    }
+}
+
+impl ComparisonLine {
+    pub fn base_coverage(&self) -> LineCoverage {
+        LineCoverage::from_type_code(self.coverage.base)
+    }
+
+    pub fn head_coverage(&self) -> LineCoverage {
+        LineCoverage::from_type_code(self.coverage.head)
+    }
+}
+
+fn missed_changed_lines(lines: &[ComparisonLine]) -> Vec<usize> {
+    lines
+        .iter()
+        .filter(|line| line.added && line.head_coverage() == LineCoverage::Miss)
+        .filter_map(|line| line.number.head)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_query() {
+        assert_eq!(
+            CompareTarget::commits("aaa", "bbb").query(),
+            vec![
+                ("base", Some("aaa".to_string())),
+                ("head", Some("bbb".to_string()))
+            ]
+        );
+        assert_eq!(
+            CompareTarget::pull(12).query(),
+            vec![("pullid", Some("12".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_comparison() {
+        let j = json!({
+            "base_commit": "5a4b2987ca3a8a7b54efac914fd72455ebff50ba",
+            "head_commit": "1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+            "totals": {
+                "base": {"files": 2, "lines": 10, "hits": 8, "misses": 2, "partials": 0, "coverage": 80.0},
+                "head": {"files": 2, "lines": 12, "hits": 9, "misses": 3, "partials": 0, "coverage": 75.0},
+                "patch": {"files": 1, "lines": 2, "hits": 1, "misses": 1, "partials": 0, "coverage": 50.0}
+            },
+            "commit_uploads": [],
+            "diff": {"git_commits": []},
+            "files": [
+                {
+                    "name": {"base": "src/lib.rs", "head": "src/lib.rs"},
+                    "totals": {
+                        "base": {"lines": 5, "hits": 4, "misses": 1, "partials": 0, "coverage": 80.0},
+                        "head": {"lines": 7, "hits": 5, "misses": 2, "partials": 0, "coverage": 71.42857},
+                        "patch": {"lines": 2, "hits": 1, "misses": 1, "partials": 0, "coverage": 50.0}
+                    },
+                    "has_diff": true,
+                    "stats": {"added": 2, "removed": 0},
+                    "change_summary": {},
+                    "lines": [
+                        {
+                            "value": " fn main() {",
+                            "number": {"base": 1, "head": 1},
+                            "coverage": {"base": 0, "head": 0},
+                            "is_diff": false,
+                            "added": false,
+                            "removed": false,
+                            "sessions": 1
+                        },
+                        {
+                            "value": "+    run();",
+                            "number": {"base": null, "head": 2},
+                            "coverage": {"base": null, "head": 0},
+                            "is_diff": true,
+                            "added": true,
+                            "removed": false,
+                            "sessions": 1
+                        },
+                        {
+                            "value": "+    exit();",
+                            "number": {"base": null, "head": 3},
+                            "coverage": {"base": null, "head": 1},
+                            "is_diff": true,
+                            "added": true,
+                            "removed": false,
+                            "sessions": 0
+                        }
+                    ]
+                },
+                {
+                    "name": {"base": "src/other.rs", "head": "src/other.rs"},
+                    "totals": {
+                        "base": {"lines": 5, "hits": 4, "misses": 1, "partials": 0, "coverage": 80.0},
+                        "head": {"lines": 5, "hits": 4, "misses": 1, "partials": 0, "coverage": 80.0},
+                        "patch": null
+                    },
+                    "has_diff": false,
+                    "stats": null,
+                    "lines": []
+                }
+            ],
+            "untracked": [],
+            "has_unmerged_base_commits": false
+        });
+        let comparison = serde_json::from_value::<Comparison>(j).unwrap();
+        assert_eq!(comparison.coverage_change(), Some(-5.0));
+        assert_eq!(comparison.totals.patch_coverage(), Some(50.0));
+        let changed: Vec<&ComparisonFile> = comparison.changed_files().collect();
+        assert_eq!(changed.len(), 1);
+        let file = changed[0];
+        assert_eq!(file.name.head.as_deref(), Some("src/lib.rs"));
+        assert_eq!(file.lines_added(), 2);
+        assert_eq!(file.lines_removed(), 0);
+        assert_eq!(file.coverage_before(), Some(80.0));
+        assert_eq!(file.coverage_after(), Some(71.42857));
+        assert_eq!(file.missed_changed_lines(), vec![3]);
+    }
+
+    #[test]
+    fn test_deserialize_segments() {
+        let j = json!({
+            "segments": [
+                {
+                    "header": "-1,1 +1,3",
+                    "has_unintended_changes": false,
+                    "lines": [
+                        {
+                            "value": " fn main() {",
+                            "number": {"base": 1, "head": 1},
+                            "coverage": {"base": 1, "head": 0},
+                            "is_diff": false,
+                            "added": false,
+                            "removed": false,
+                            "sessions": 1
+                        },
+                        {
+                            "value": "+    exit();",
+                            "number": {"base": null, "head": 2},
+                            "coverage": {"base": null, "head": 1},
+                            "is_diff": true,
+                            "added": true,
+                            "removed": false,
+                            "sessions": 0
+                        }
+                    ]
+                }
+            ]
+        });
+        let segments = serde_json::from_value::<FileSegments>(j).unwrap();
+        let segment = &segments.segments[0];
+        assert_eq!(segment.header.as_deref(), Some("-1,1 +1,3"));
+        assert_eq!(segment.lines[0].base_coverage(), LineCoverage::Miss);
+        assert_eq!(segment.lines[0].head_coverage(), LineCoverage::Hit(None));
+        assert_eq!(segment.lines[1].base_coverage(), LineCoverage::Ignored);
+        assert_eq!(segments.missed_changed_lines(), vec![2]);
+    }
+
+    #[test]
+    fn test_deserialize_breakdowns() {
+        let j = json!([
+            {
+                "name": "unit",
+                "base_report_totals": {"files": 2, "lines": 10, "hits": 8, "misses": 2, "partials": 0, "coverage": 80.0},
+                "head_report_totals": {"files": 2, "lines": 10, "hits": 9, "misses": 1, "partials": 0, "coverage": 90.0},
+                "diff_totals": {"files": 1, "lines": 1, "hits": 1, "misses": 0, "partials": 0, "coverage": 100.0}
+            },
+            {
+                "name": "integration",
+                "base_report_totals": null,
+                "head_report_totals": {"files": 1, "lines": 4, "hits": 2, "misses": 2, "partials": 0, "coverage": 50.0},
+                "diff_totals": null
+            }
+        ]);
+        let flags = serde_json::from_value::<Vec<FlagComparison>>(j).unwrap();
+        assert_eq!(flags[0].totals.coverage_change(), Some(10.0));
+        assert_eq!(flags[0].totals.diff_coverage(), Some(100.0));
+        assert_eq!(flags[1].totals.coverage_change(), None);
+
+        let j = json!([
+            {
+                "component_id": "backend",
+                "name": "Backend",
+                "base_report_totals": {"coverage": 70.0},
+                "head_report_totals": {"coverage": 75.0},
+                "diff_totals": null
+            }
+        ]);
+        let components = serde_json::from_value::<Vec<ComponentComparison>>(j).unwrap();
+        assert_eq!(components[0].component_id, "backend");
+        assert_eq!(components[0].totals.coverage_change(), Some(5.0));
+
+        let j = json!({
+            "files": [
+                {
+                    "file_name": "lib.rs",
+                    "base_name": "src/lib.rs",
+                    "head_name": "src/lib.rs",
+                    "base_coverage": {"lines": 5, "hits": 4, "misses": 1, "partials": 0, "coverage": 80.0},
+                    "head_coverage": {"lines": 7, "hits": 5, "misses": 2, "partials": 0, "coverage": 71.42857},
+                    "patch_coverage": {"lines": 2, "hits": 1, "misses": 1, "partials": 0, "coverage": 50.0},
+                    "change_coverage": -8.57143,
+                    "file_was_added_by_diff": false,
+                    "file_was_removed_by_diff": false
+                }
+            ],
+            "state": "processed"
+        });
+        let impacted = serde_json::from_value::<ImpactedFiles>(j).unwrap();
+        assert_eq!(impacted.state.as_deref(), Some("processed"));
+        assert_eq!(impacted.files[0].head_name.as_deref(), Some("src/lib.rs"));
+        assert_eq!(impacted.files[0].change_coverage, Some(-8.57143));
+    }
+}
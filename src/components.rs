@@ -0,0 +1,41 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/components endpoint returns the components defined in codecov.yml.
+ * https://docs.codecov.com/docs/components
+ */
+use serde::{Deserialize, Serialize};
+
+/**
+ * ComponentsAPIResponse is the list of components returned by the components API.
+ * Unlike most list APIs, it is not paginated.
+ */
+pub type ComponentsAPIResponse = Vec<Component>;
+
+/**
+ * Component is a path-based grouping of files. e.g. frontend, backend
+ * component_id is the id used to filter reports and totals.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Component {
+    pub component_id: String,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_components() {
+        let j = json!([
+            {"component_id": "frontend", "name": "Frontend"},
+            {"component_id": "backend", "name": "Backend"}
+        ]);
+        let components = serde_json::from_value::<ComponentsAPIResponse>(j).unwrap();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[1].component_id, "backend");
+        assert_eq!(components[1].name, "Backend");
+    }
+}
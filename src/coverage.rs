@@ -0,0 +1,161 @@
+/**
+ * Codecov v2 API
+ * Coverage trend endpoints return coverage aggregated by interval.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::pagination::Paginated;
+use crate::timestamp::Timestamp;
+
+/**
+ * CoverageAPIResponse is a page of coverage points returned by the coverage trend APIs.
+ */
+pub type CoverageAPIResponse = Paginated<CoveragePoint>;
+
+/**
+ * CoveragePoint is the coverage aggregated over an interval starting at timestamp.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CoveragePoint {
+    pub timestamp: Timestamp,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+/**
+ * Interval is the aggregation interval of a coverage trend.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "7d")]
+    SevenDays,
+    #[serde(rename = "30d")]
+    ThirtyDays,
+}
+
+impl Interval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::OneDay => "1d",
+            Interval::SevenDays => "7d",
+            Interval::ThirtyDays => "30d",
+        }
+    }
+}
+
+/**
+ * CoverageQuery holds the filters of the coverage trend APIs.
+ * start_date and end_date are dates or datetimes in ISO 8601. e.g. 2023-08-01
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageQuery {
+    pub interval: Interval,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub branch: Option<String>,
+    pub flags: Vec<String>,
+}
+
+impl CoverageQuery {
+    pub fn new(interval: Interval) -> CoverageQuery {
+        CoverageQuery {
+            interval,
+            start_date: None,
+            end_date: None,
+            branch: None,
+            flags: vec![],
+        }
+    }
+
+    pub fn start_date(mut self, start_date: &str) -> CoverageQuery {
+        self.start_date = Some(start_date.to_string());
+        self
+    }
+
+    pub fn end_date(mut self, end_date: &str) -> CoverageQuery {
+        self.end_date = Some(end_date.to_string());
+        self
+    }
+
+    pub fn branch(mut self, branch: &str) -> CoverageQuery {
+        self.branch = Some(branch.to_string());
+        self
+    }
+
+    /**
+     * flag adds a flag filter. It can be called more than once.
+     */
+    pub fn flag(mut self, flag: &str) -> CoverageQuery {
+        self.flags.push(flag.to_string());
+        self
+    }
+
+    /**
+     * query returns the query parameters of the coverage trend APIs.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn query(&self) -> Vec<(&'static str, Option<String>)> {
+        let mut query = vec![
+            ("interval", Some(self.interval.as_str().to_string())),
+            ("start_date", self.start_date.clone()),
+            ("end_date", self.end_date.clone()),
+            ("branch", self.branch.clone()),
+        ];
+        for flag in &self.flags {
+            query.push(("flags", Some(flag.to_string())));
+        }
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_query() {
+        let query = CoverageQuery::new(Interval::SevenDays)
+            .start_date("2023-07-01")
+            .branch("main")
+            .flag("unit")
+            .flag("integration");
+        assert_eq!(
+            query.query(),
+            vec![
+                ("interval", Some("7d".to_string())),
+                ("start_date", Some("2023-07-01".to_string())),
+                ("end_date", None),
+                ("branch", Some("main".to_string())),
+                ("flags", Some("unit".to_string())),
+                ("flags", Some("integration".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_coverage() {
+        let j = json!({
+            "count": 2,
+            "next": null,
+            "previous": null,
+            "results": [
+                {"timestamp": "2023-07-31T00:00:00Z", "min": 80.0, "max": 86.05, "avg": 83.2},
+                {"timestamp": "2023-08-01T00:00:00Z", "min": 86.05, "max": 86.05, "avg": 86.05}
+            ],
+            "total_pages": 1
+        });
+        let coverage = serde_json::from_value::<CoverageAPIResponse>(j).unwrap();
+        assert_eq!(coverage.results.len(), 2);
+        assert_eq!(coverage.results[0].avg, 83.2);
+        assert_eq!(
+            serde_json::from_value::<Interval>(json!("30d")).unwrap(),
+            Interval::ThirtyDays
+        );
+    }
+}
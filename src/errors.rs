@@ -1,3 +1,6 @@
+use std::fmt;
+use std::time::Duration;
+
 /**
  * Error is an enum wrapping all possible errors.
  */
@@ -6,4 +9,222 @@ pub enum Error {
     ReqwestError(reqwest::Error),
     EnvError(std::env::VarError),
     DeserializeError(serde_json::Error),
+    IoError(std::io::Error),
+    /**
+     * A service name, slug or URL could not be parsed.
+     */
+    ParseError(String),
+    /**
+     * 401 Unauthorized. The token is missing or invalid.
+     */
+    Unauthorized,
+    /**
+     * 403 Forbidden. The token is not allowed to access the resource.
+     */
+    Forbidden,
+    /**
+     * 404 Not Found. detail is the message returned by the API.
+     */
+    NotFound {
+        detail: String,
+    },
+    /**
+     * 429 Too Many Requests. retry_after is taken from the Retry-After header.
+     */
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    /**
+     * 5xx Server Error.
+     */
+    Server {
+        status: u16,
+        body: String,
+    },
+    /**
+     * Any other non-success status.
+     */
+    UnexpectedStatus {
+        status: u16,
+        body: String,
+    },
+}
+
+impl Error {
+    /**
+     * from_status returns an Error for a non-success HTTP response.
+     * retry_after is the raw value of the Retry-After header.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn from_status(status: u16, retry_after: Option<&str>, body: String) -> Error {
+        match status {
+            401 => Error::Unauthorized,
+            403 => Error::Forbidden,
+            404 => Error::NotFound {
+                detail: detail_from_body(body),
+            },
+            429 => Error::RateLimited {
+                retry_after: retry_after.and_then(parse_retry_after),
+            },
+            500..=599 => Error::Server { status, body },
+            _ => Error::UnexpectedStatus { status, body },
+        }
+    }
+
+    /**
+     * status returns the HTTP status code if the error comes from an HTTP response.
+     */
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Unauthorized => Some(401),
+            Error::Forbidden => Some(403),
+            Error::NotFound { .. } => Some(404),
+            Error::RateLimited { .. } => Some(429),
+            Error::Server { status, .. } => Some(*status),
+            Error::UnexpectedStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * The API returns {"detail": "..."} for errors. Falls back to the raw body.
+ */
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn detail_from_body(body: String) -> String {
+    match serde_json::from_str::<serde_json::Value>(&body) {
+        Ok(serde_json::Value::Object(map)) => match map.get("detail") {
+            Some(serde_json::Value::String(detail)) => detail.to_string(),
+            _ => body,
+        },
+        _ => body,
+    }
+}
+
+/**
+ * Retry-After is given in seconds by the Codecov API.
+ */
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ReqwestError(e) => write!(f, "request error: {}", e),
+            Error::EnvError(e) => write!(f, "environment variable error: {}", e),
+            Error::DeserializeError(e) => write!(f, "deserialize error: {}", e),
+            Error::IoError(e) => write!(f, "io error: {}", e),
+            Error::ParseError(message) => write!(f, "parse error: {}", message),
+            Error::Unauthorized => write!(f, "unauthorized: check the Codecov API token"),
+            Error::Forbidden => write!(f, "forbidden"),
+            Error::NotFound { detail } => write!(f, "not found: {}", detail),
+            Error::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "rate limited: retry after {} seconds",
+                retry_after.as_secs()
+            ),
+            Error::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            Error::Server { status, body } => write!(f, "server error ({}): {}", status, body),
+            Error::UnexpectedStatus { status, body } => {
+                write!(f, "unexpected status ({}): {}", status, body)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::ReqwestError(e) => Some(e),
+            Error::EnvError(e) => Some(e),
+            Error::DeserializeError(e) => Some(e),
+            Error::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Error {
+        Error::ReqwestError(e)
+    }
+}
+
+impl From<std::env::VarError> for Error {
+    fn from(e: std::env::VarError) -> Error {
+        Error::EnvError(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Error {
+        Error::DeserializeError(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::IoError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_from_status() {
+        assert!(matches!(
+            Error::from_status(401, None, String::new()),
+            Error::Unauthorized
+        ));
+        assert!(matches!(
+            Error::from_status(403, None, String::new()),
+            Error::Forbidden
+        ));
+        match Error::from_status(404, None, r#"{"detail": "Not found."}"#.to_string()) {
+            Error::NotFound { detail } => assert_eq!(detail, "Not found."),
+            e => panic!("unexpected error: {:?}", e),
+        }
+        match Error::from_status(404, None, "<html></html>".to_string()) {
+            Error::NotFound { detail } => assert_eq!(detail, "<html></html>"),
+            e => panic!("unexpected error: {:?}", e),
+        }
+        match Error::from_status(429, Some("30"), String::new()) {
+            Error::RateLimited { retry_after } => {
+                assert_eq!(retry_after, Some(Duration::from_secs(30)))
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+        match Error::from_status(502, None, "Bad Gateway".to_string()) {
+            Error::Server { status, body } => {
+                assert_eq!(status, 502);
+                assert_eq!(body, "Bad Gateway");
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+        let e = Error::from_status(400, None, String::new());
+        assert_eq!(e.status(), Some(400));
+        assert!(matches!(e, Error::UnexpectedStatus { .. }));
+    }
+
+    #[test]
+    fn test_display() {
+        let e = Error::NotFound {
+            detail: "No Branch matches the given query.".to_string(),
+        };
+        assert_eq!(
+            e.to_string(),
+            "not found: No Branch matches the given query."
+        );
+        let e = Error::RateLimited {
+            retry_after: Some(Duration::from_secs(5)),
+        };
+        assert_eq!(e.to_string(), "rate limited: retry after 5 seconds");
+    }
 }
@@ -0,0 +1,190 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/file_report/{path} endpoint returns the line-by-line coverage of a file.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::totals::Totals;
+
+/**
+ * CommitRef selects the commit of a file report, either by sha or by the head of a branch.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitRef {
+    Sha(String),
+    Branch(String),
+}
+
+impl CommitRef {
+    pub fn sha(sha: &str) -> CommitRef {
+        CommitRef::Sha(sha.to_string())
+    }
+
+    pub fn branch(branch: &str) -> CommitRef {
+        CommitRef::Branch(branch.to_string())
+    }
+
+    /**
+     * query returns the query parameters of the file report API.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn query(&self) -> Vec<(&'static str, Option<String>)> {
+        match self {
+            CommitRef::Sha(sha) => vec![("sha", Some(sha.to_string()))],
+            CommitRef::Branch(branch) => vec![("branch", Some(branch.to_string()))],
+        }
+    }
+}
+
+/**
+ * FileReport is a struct that represents the response from the file report API.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FileReport {
+    pub name: String,
+    pub totals: Totals,
+    #[serde(default)]
+    pub line_coverage: Vec<Line>,
+    pub commit_sha: Option<String>,
+    pub commit_file_url: Option<String>,
+}
+
+impl FileReport {
+    /**
+     * Returns the line numbers that are not covered by tests.
+     */
+    pub fn missed_lines(&self) -> Vec<usize> {
+        self.line_coverage
+            .iter()
+            .filter(|line| line.coverage == LineCoverage::Miss)
+            .map(|line| line.number)
+            .collect()
+    }
+}
+
+/**
+ * Line is the coverage of a single line.
+ * The API returns each line as [line number, coverage type].
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "(usize, Option<i64>)", into = "(usize, Option<i64>)")]
+pub struct Line {
+    pub number: usize,
+    pub coverage: LineCoverage,
+}
+
+impl From<(usize, Option<i64>)> for Line {
+    fn from((number, code): (usize, Option<i64>)) -> Line {
+        Line {
+            number,
+            coverage: LineCoverage::from_type_code(code),
+        }
+    }
+}
+
+impl From<Line> for (usize, Option<i64>) {
+    fn from(line: Line) -> (usize, Option<i64>) {
+        (line.number, line.coverage.type_code())
+    }
+}
+
+/**
+ * LineCoverage is the coverage of a line.
+ * Hit carries the hit count and Partial the covered and total branches when they are known.
+ * The v2 API only returns the type of a line, so they are None for lines decoded from it.
+ * Skipped (-1) and Ignored (null) are kept apart so that a Line serializes back to the same value.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCoverage {
+    Hit(Option<u64>),
+    Miss,
+    Partial {
+        covered: Option<u64>,
+        total: Option<u64>,
+    },
+    Skipped,
+    Ignored,
+}
+
+impl LineCoverage {
+    /**
+     * from_type_code decodes the coverage type of the v2 API.
+     * 0 = hit, 1 = miss, 2 = partial, -1 = skipped, null = ignored.
+     * Unknown codes are treated as ignored.
+     */
+    pub fn from_type_code(code: Option<i64>) -> LineCoverage {
+        match code {
+            Some(0) => LineCoverage::Hit(None),
+            Some(1) => LineCoverage::Miss,
+            Some(2) => LineCoverage::Partial {
+                covered: None,
+                total: None,
+            },
+            Some(-1) => LineCoverage::Skipped,
+            _ => LineCoverage::Ignored,
+        }
+    }
+
+    /**
+     * type_code returns the coverage type of the v2 API.
+     */
+    pub fn type_code(&self) -> Option<i64> {
+        match self {
+            LineCoverage::Hit(_) => Some(0),
+            LineCoverage::Miss => Some(1),
+            LineCoverage::Partial { .. } => Some(2),
+            LineCoverage::Skipped => Some(-1),
+            LineCoverage::Ignored => None,
+        }
+    }
+
+    /**
+     * Returns true if the line is at least partially covered.
+     */
+    pub fn is_covered(&self) -> bool {
+        matches!(self, LineCoverage::Hit(_) | LineCoverage::Partial { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_file_report() {
+        let j = json!({
+            "name": "src/lib.rs",
+            "totals": {"files": 1, "lines": 3, "hits": 1, "misses": 1, "partials": 1, "coverage": 33.33},
+            "line_coverage": [[1, 0], [2, 1], [3, 2], [5, null], [6, -1]],
+            "commit_sha": "1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+            "commit_file_url": "https://codecov.io/gh/codecov/codecov-demo/commit/1eb341765e7c3daa88ae5d2a751538a620c6dbce/blob/src/lib.rs"
+        });
+        let report = serde_json::from_value::<FileReport>(j).unwrap();
+        let coverage: Vec<LineCoverage> = report
+            .line_coverage
+            .iter()
+            .map(|line| line.coverage)
+            .collect();
+        assert_eq!(
+            coverage,
+            vec![
+                LineCoverage::Hit(None),
+                LineCoverage::Miss,
+                LineCoverage::Partial {
+                    covered: None,
+                    total: None
+                },
+                LineCoverage::Ignored,
+                LineCoverage::Skipped,
+            ]
+        );
+        assert_eq!(report.line_coverage[3].number, 5);
+        assert_eq!(report.missed_lines(), vec![2]);
+        assert_eq!(
+            serde_json::to_value(&report.line_coverage).unwrap(),
+            json!([[1, 0], [2, 1], [3, 2], [5, null], [6, -1]])
+        );
+    }
+}
@@ -0,0 +1,565 @@
+/**
+ * Canned API responses shared by the offline client tests.
+ */
+use serde_json::{Value, json};
+
+use crate::transport::{HttpResponse, Method, MockTransport};
+
+pub(crate) const API: &str = "https://codecov.io/api/v2";
+
+pub(crate) fn url(path: &str) -> String {
+    format!("{}{}", API, path)
+}
+
+pub(crate) fn page(results: Vec<Value>, next: Option<&str>, total_pages: usize) -> Value {
+    json!({
+        "results": results,
+        "count": results.len(),
+        "next": next.map(url),
+        "previous": null,
+        "total_pages": total_pages,
+    })
+}
+
+pub(crate) fn totals(coverage: f64) -> Value {
+    json!({
+        "files": 10,
+        "lines": 172,
+        "hits": 148,
+        "misses": 23,
+        "partials": 1,
+        "coverage": coverage,
+        "branches": 22,
+        "methods": 0,
+        "sessions": 1,
+        "complexity": 0.0,
+        "complexity_total": 0.0,
+        "complexity_ratio": 0,
+        "diff": 0
+    })
+}
+
+pub(crate) fn repo(name: &str) -> Value {
+    json!({
+        "name": name,
+        "private": false,
+        "updatestamp": "2023-08-01T19:10:56.045522Z",
+        "author": {
+            "service": "github",
+            "username": "codecov",
+            "name": "codecov"
+        },
+        "language": "python",
+        "branch": "main",
+        "active": true,
+        "activated": true
+    })
+}
+
+pub(crate) fn commit(commitid: &str) -> Value {
+    json!({
+        "commitid": commitid,
+        "message": "Update README.md",
+        "timestamp": "2023-08-01T15:41:47Z",
+        "ci_passed": true,
+        "author": {
+            "service": "github",
+            "username": "codecov",
+            "name": "Codecov"
+        },
+        "branch": "main",
+        "totals": totals(86.05),
+        "state": "complete",
+        "parent": "5a4b2987ca3a8a7b54efac914fd72455ebff50ba"
+    })
+}
+
+pub(crate) fn branch(name: &str) -> Value {
+    json!({
+        "name": name,
+        "updatestamp": "2023-08-01T19:10:56.045522Z"
+    })
+}
+
+pub(crate) fn branch_detail(name: &str) -> Value {
+    json!({
+        "head_commit": {
+            "author": {
+                "name": "renovate[bot]",
+                "service": "github",
+                "username": null
+            },
+            "branch": name,
+            "ci_passed": true,
+            "commitid": "1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+            "message": "chore(deps): update dependency @swc/core to v1.3.73",
+            "parent": "5a4b2987ca3a8a7b54efac914fd72455ebff50ba",
+            "report": {
+                "files": [],
+                "totals": totals(86.05)
+            },
+            "state": "complete",
+            "timestamp": "2023-08-01T15:41:47Z",
+            "totals": totals(86.05)
+        },
+        "name": name,
+        "updatestamp": "2023-08-01T19:10:56.045522Z"
+    })
+}
+
+pub(crate) fn commit_detail(commitid: &str) -> Value {
+    let mut detail = commit(commitid);
+    detail["report"] = json!({
+        "totals": totals(86.05),
+        "files": [
+            {
+                "name": "src/lib.rs",
+                "totals": totals(66.67),
+                "line_coverage": [[1, 0], [2, 0], [4, 1]]
+            }
+        ]
+    });
+    detail
+}
+
+pub(crate) fn comparison(base: &str, head: &str) -> Value {
+    json!({
+        "base_commit": base,
+        "head_commit": head,
+        "totals": {
+            "base": totals(80.0),
+            "head": totals(86.05),
+            "patch": totals(50.0)
+        },
+        "commit_uploads": [],
+        "diff": {"git_commits": []},
+        "files": [
+            {
+                "name": {"base": "src/lib.rs", "head": "src/lib.rs"},
+                "totals": {
+                    "base": totals(80.0),
+                    "head": totals(86.05),
+                    "patch": totals(50.0)
+                },
+                "has_diff": true,
+                "stats": {"added": 2, "removed": 1},
+                "lines": [
+                    {
+                        "value": "+    run();",
+                        "number": {"base": null, "head": 1},
+                        "coverage": {"base": null, "head": 0},
+                        "is_diff": true,
+                        "added": true,
+                        "removed": false,
+                        "sessions": 1
+                    },
+                    {
+                        "value": "+    exit();",
+                        "number": {"base": null, "head": 2},
+                        "coverage": {"base": null, "head": 1},
+                        "is_diff": true,
+                        "added": true,
+                        "removed": false,
+                        "sessions": 0
+                    }
+                ]
+            }
+        ],
+        "untracked": [],
+        "has_unmerged_base_commits": false
+    })
+}
+
+pub(crate) fn user(username: &str, activated: bool) -> Value {
+    json!({
+        "service": "github",
+        "username": username,
+        "name": username,
+        "activated": activated,
+        "is_admin": false,
+        "email": null
+    })
+}
+
+pub(crate) fn pull(pullid: u64, state: &str) -> Value {
+    json!({
+        "pullid": pullid,
+        "title": "Add compare endpoint",
+        "base_totals": totals(80.0),
+        "head_totals": totals(86.05),
+        "updatestamp": "2023-08-01T19:10:56.045522Z",
+        "state": state,
+        "ci_passed": true,
+        "author": {
+            "service": "github",
+            "username": "codecov",
+            "name": "Codecov"
+        }
+    })
+}
+
+/**
+ * mock_transport returns a MockTransport serving the github/codecov owner
+ * and the github/codecov/codecov-demo repo.
+ */
+pub(crate) fn mock_transport() -> MockTransport {
+    let demo = "/github/codecov/repos/codecov-demo";
+    MockTransport::new()
+        .with_json(
+            &url("/github/codecov/repos?page_size=100"),
+            page(
+                vec![repo("codecov-demo"), repo("example-python")],
+                Some("/github/codecov/repos?page=2&page_size=100"),
+                2,
+            ),
+        )
+        .with_json(
+            &url("/github/codecov/repos?page=2&page_size=100"),
+            page(vec![repo("example-rust")], None, 2),
+        )
+        .with_json(
+            &url("/github/"),
+            page(
+                vec![json!({"service": "github", "username": "codecov", "name": "Codecov"})],
+                None,
+                1,
+            ),
+        )
+        .with_json(
+            &url("/github/codecov"),
+            json!({"service": "github", "username": "codecov", "name": "Codecov"}),
+        )
+        .with_json(
+            &url("/github/codecov/users?activated=false"),
+            page(vec![user("kitsuyui", false)], None, 1),
+        )
+        .with_json(&url("/github/codecov/users/kitsuyui"), user("kitsuyui", false))
+        .with_response(
+            Method::Patch,
+            &url("/github/codecov/users/kitsuyui"),
+            HttpResponse::json(&user("kitsuyui", true)),
+        )
+        .with_json(&url(demo), {
+            let mut detail = repo("codecov-demo");
+            detail["totals"] = totals(86.05);
+            detail
+        })
+        .with_json(
+            &url(&format!("{}/config", demo)),
+            json!({"upload_token": "upload-token", "graph_token": "graph-token"}),
+        )
+        .with_json(
+            &url(&format!("{}/commits", demo)),
+            page(
+                vec![commit("1eb341765e7c3daa88ae5d2a751538a620c6dbce")],
+                None,
+                1,
+            ),
+        )
+        .with_json(
+            &url(&format!("{}/commits?page_size=2", demo)),
+            page(
+                vec![commit("aaa"), commit("bbb")],
+                Some(&format!("{}/commits?page=2&page_size=2", demo)),
+                3,
+            ),
+        )
+        .with_json(
+            &url(&format!("{}/commits?page=2&page_size=2", demo)),
+            page(
+                vec![commit("ccc"), commit("ddd")],
+                Some(&format!("{}/commits?page=3&page_size=2", demo)),
+                3,
+            ),
+        )
+        .with_json(
+            &url(&format!("{}/commits?page=3&page_size=2", demo)),
+            page(vec![commit("eee")], None, 3),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/commits/1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+                demo
+            )),
+            commit_detail("1eb341765e7c3daa88ae5d2a751538a620c6dbce"),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/commits/1eb341765e7c3daa88ae5d2a751538a620c6dbce/uploads",
+                demo
+            )),
+            page(
+                vec![
+                    json!({
+                        "id": 1,
+                        "created_at": "2023-08-01T15:42:00Z",
+                        "state": "processed",
+                        "provider": "github-actions",
+                        "build_url": "https://github.com/codecov/codecov-demo/actions/runs/5735069843",
+                        "flags": ["unit"],
+                        "totals": totals(90.0)
+                    }),
+                    json!({
+                        "id": 2,
+                        "created_at": "2023-08-01T15:44:00Z",
+                        "state": "error",
+                        "provider": "github-actions",
+                        "flags": ["integration"],
+                        "totals": null
+                    }),
+                ],
+                None,
+                1,
+            ),
+        )
+        .with_json(
+            &url(&format!("{}/compare?base=aaa&head=bbb", demo)),
+            comparison("aaa", "bbb"),
+        )
+        .with_json(
+            &url(&format!("{}/compare?pullid=12", demo)),
+            comparison("aaa", "bbb"),
+        )
+        .with_json(
+            &url(&format!("{}/compare/file/src/lib.rs?pullid=12", demo)),
+            comparison("aaa", "bbb")["files"][0].clone(),
+        )
+        .with_json(
+            &url(&format!("{}/compare/segments/src/lib.rs?pullid=12", demo)),
+            json!({
+                "segments": [
+                    {
+                        "header": "-0,0 +1,2",
+                        "has_unintended_changes": false,
+                        "lines": comparison("aaa", "bbb")["files"][0]["lines"].clone()
+                    }
+                ]
+            }),
+        )
+        .with_json(
+            &url(&format!("{}/compare/impacted_files?pullid=12", demo)),
+            json!({
+                "files": [
+                    {
+                        "file_name": "lib.rs",
+                        "base_name": "src/lib.rs",
+                        "head_name": "src/lib.rs",
+                        "base_coverage": totals(80.0),
+                        "head_coverage": totals(86.05),
+                        "patch_coverage": totals(50.0),
+                        "change_coverage": 6.05,
+                        "file_was_added_by_diff": false,
+                        "file_was_removed_by_diff": false
+                    }
+                ],
+                "state": "processed"
+            }),
+        )
+        .with_json(
+            &url(&format!("{}/compare/flags?pullid=12", demo)),
+            json!([
+                {
+                    "name": "unit",
+                    "base_report_totals": totals(80.0),
+                    "head_report_totals": totals(90.0),
+                    "diff_totals": totals(100.0)
+                }
+            ]),
+        )
+        .with_json(
+            &url(&format!("{}/compare/components?pullid=12", demo)),
+            json!([
+                {
+                    "component_id": "backend",
+                    "name": "Backend",
+                    "base_report_totals": totals(70.0),
+                    "head_report_totals": totals(75.0),
+                    "diff_totals": totals(50.0)
+                }
+            ]),
+        )
+        .with_json(
+            &url(&format!("{}/pulls?state=open", demo)),
+            page(vec![pull(12, "open")], None, 1),
+        )
+        .with_json(&url(&format!("{}/pulls/12", demo)), pull(12, "open"))
+        .with_json(
+            &url(&format!("{}/flags", demo)),
+            page(
+                vec![
+                    json!({"flag_name": "unit"}),
+                    json!({"flag_name": "integration"}),
+                ],
+                None,
+                1,
+            ),
+        )
+        .with_json(
+            &url(&format!("{}/totals?flag=unit", demo)),
+            json!({"totals": totals(90.0), "files": [], "commit_file_url": null}),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/flags/unit/coverage?interval=1d&branch=main",
+                demo
+            )),
+            page(
+                vec![
+                    json!({"timestamp": "2023-07-31T00:00:00Z", "min": 85.0, "max": 88.0, "avg": 86.5}),
+                    json!({"timestamp": "2023-08-01T00:00:00Z", "min": 90.0, "max": 90.0, "avg": 90.0}),
+                ],
+                None,
+                1,
+            ),
+        )
+        .with_json(
+            &url(&format!("{}/components", demo)),
+            json!([
+                {"component_id": "frontend", "name": "Frontend"},
+                {"component_id": "backend", "name": "Backend"}
+            ]),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/report?branch=main&path=src%2Fpayments%2F",
+                demo
+            )),
+            json!({
+                "totals": totals(66.67),
+                "files": [
+                    {
+                        "name": "src/payments/charge.rs",
+                        "totals": totals(50.0),
+                        "line_coverage": [[1, 0], [2, 1]]
+                    },
+                    {
+                        "name": "src/payments/refund.rs",
+                        "totals": totals(100.0),
+                        "line_coverage": [[1, 0]]
+                    }
+                ],
+                "commit_file_url": "https://codecov.io/gh/codecov/codecov-demo/commit/1eb341765e7c3daa88ae5d2a751538a620c6dbce/tree"
+            }),
+        )
+        .with_json(
+            &url(&format!("{}/report/tree?branch=main&path=src&depth=2", demo)),
+            json!([
+                {
+                    "name": "payments",
+                    "full_path": "src/payments",
+                    "coverage": 66.67,
+                    "lines": 3,
+                    "hits": 2,
+                    "partials": 0,
+                    "misses": 1,
+                    "children": [
+                        {
+                            "name": "charge.rs",
+                            "full_path": "src/payments/charge.rs",
+                            "coverage": 50.0,
+                            "lines": 2,
+                            "hits": 1,
+                            "partials": 0,
+                            "misses": 1
+                        },
+                        {
+                            "name": "refund.rs",
+                            "full_path": "src/payments/refund.rs",
+                            "coverage": 100.0,
+                            "lines": 1,
+                            "hits": 1,
+                            "partials": 0,
+                            "misses": 0
+                        }
+                    ]
+                }
+            ]),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/file_report/src/payments/charge.rs?branch=main",
+                demo
+            )),
+            json!({
+                "name": "src/payments/charge.rs",
+                "totals": totals(50.0),
+                "line_coverage": [[1, 0], [2, 1], [3, null]],
+                "commit_sha": "1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+                "commit_file_url": null
+            }),
+        )
+        .with_json(
+            &url(&format!("{}/report?component_id=backend", demo)),
+            json!({
+                "totals": totals(75.0),
+                "files": [{"name": "api/server.py", "totals": totals(75.0)}],
+                "commit_file_url": null
+            }),
+        )
+        .with_json(
+            &url(&format!("{}/totals?component_id=backend", demo)),
+            json!({"totals": totals(75.0), "files": [], "commit_file_url": null}),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/coverage?interval=7d&start_date=2023-07-01&end_date=2023-08-01&branch=main&flags=unit",
+                demo
+            )),
+            page(
+                vec![
+                    json!({"timestamp": "2023-07-24T00:00:00Z", "min": 80.0, "max": 86.05, "avg": 83.2}),
+                    json!({"timestamp": "2023-07-31T00:00:00Z", "min": 86.05, "max": 86.05, "avg": 86.05}),
+                ],
+                None,
+                1,
+            ),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/test-results?branch=main&interval=7d&page_size=1",
+                demo
+            )),
+            page(
+                vec![json!({
+                    "name": "tests::test_parse",
+                    "testsuite": "codecov",
+                    "flags": ["unit"],
+                    "failure_rate": 0.25,
+                    "avg_duration": 0.012,
+                    "last_run_commit": "1eb341765e7c3daa88ae5d2a751538a620c6dbce"
+                })],
+                Some(&format!(
+                    "{}/test-results?branch=main&interval=7d&page=2&page_size=1",
+                    demo
+                )),
+                2,
+            ),
+        )
+        .with_json(
+            &url(&format!(
+                "{}/test-results?branch=main&interval=7d&page=2&page_size=1",
+                demo
+            )),
+            page(
+                vec![json!({"name": "tests::test_ok", "failure_rate": 0.0})],
+                None,
+                2,
+            ),
+        )
+        .with_json(
+            &url(&format!("{}/branches", demo)),
+            page(vec![branch("main"), branch("develop")], None, 1),
+        )
+        .with_json(
+            &url(&format!("{}/branches/main", demo)),
+            branch_detail("main"),
+        )
+        .with_response(
+            Method::Get,
+            &url(&format!("{}/branches/aaaaaaaaaa", demo)),
+            HttpResponse::new(
+                404,
+                &json!({"detail": "No Branch matches the given query."}).to_string(),
+            ),
+        )
+}
@@ -0,0 +1,53 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/flags endpoint returns the flags uploaded for a given repo.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::pagination::Paginated;
+use crate::totals::Totals;
+
+/**
+ * FlagsAPIResponse is a page of flags returned by the flags API.
+ */
+pub type FlagsAPIResponse = Paginated<Flag>;
+
+/**
+ * Flag is a struct that represents a flag. e.g. unit, integration
+ * totals is the latest totals of the flag if the API returns them.
+ * Use Client::get_flag_totals to fetch them otherwise.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Flag {
+    pub flag_name: String,
+    #[serde(default)]
+    pub totals: Option<Totals>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_flags() {
+        let j = json!({
+            "count": 2,
+            "next": null,
+            "previous": null,
+            "results": [
+                {"flag_name": "unit"},
+                {"flag_name": "integration", "totals": {"files": 3, "lines": 30, "hits": 27, "misses": 3, "partials": 0, "coverage": 90.0}}
+            ],
+            "total_pages": 1
+        });
+        let flags = serde_json::from_value::<FlagsAPIResponse>(j).unwrap();
+        assert_eq!(flags.results[0].flag_name, "unit");
+        assert!(flags.results[0].totals.is_none());
+        assert_eq!(
+            flags.results[1].totals.as_ref().and_then(|t| t.coverage),
+            Some(90.0)
+        );
+    }
+}
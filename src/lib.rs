@@ -1,21 +1,49 @@
+#[cfg(feature = "async")]
+pub mod async_client;
 pub mod author;
 pub mod branch_detail;
 pub mod branches;
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub mod builder;
+pub mod cassette;
+#[cfg(feature = "blocking")]
 pub mod client;
+pub mod commit_detail;
 pub mod commits;
+pub mod compare;
+pub mod components;
+pub mod coverage;
 pub mod errors;
+pub mod file_report;
+#[cfg(test)]
+mod fixtures;
+pub mod flags;
 pub mod owner;
+pub mod pagination;
+pub mod pulls;
+pub mod report;
 pub mod repos;
+pub mod retry;
+pub mod secret;
+pub mod service;
+pub mod test_results;
+pub mod timestamp;
 pub mod totals;
+pub mod transport;
+pub mod uploads;
 pub mod url;
+pub mod users;
 
 /**
-```
-use codecov::{Client, owner::Owner};
+The blocking Client is enabled by the default `blocking` feature.
+Enable the `async` feature to use AsyncClient instead.
+
+```no_run
+use codecov::{Client, Service, owner::Owner};
 
 // let client = Client::new("1234-5678-9012-3456"); // Set token directly
 let client = Client::new_from_env().unwrap();  // Read CODECOV_OWNER_TOKEN from environment variable
-let owner = Owner::new("github", "kitsuyui");
+let owner = Owner::new(Service::GitHub, "kitsuyui");
 let repos = client.get_all_repos(&owner).unwrap();
 println!("{:?}", repos.len());
 
@@ -25,4 +53,11 @@ println!("{:?}", repo_detail);
 println!("{}", repo_detail.latest_coverage());
 ```
  */
+#[cfg(feature = "blocking")]
 pub use client::Client;
+
+#[cfg(feature = "async")]
+pub use async_client::AsyncClient;
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub use builder::ClientBuilder;
+pub use service::Service;
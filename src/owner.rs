@@ -1,16 +1,29 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
 use crate::author::Author;
+use crate::errors::Error;
+use crate::pagination::Paginated;
+use crate::service::{Service, slug_segments};
+
+/**
+ * OwnersAPIResponse is a page of owners returned by the owners API.
+ */
+pub type OwnersAPIResponse = Paginated<OwnerDetail>;
+
 /**
  * Owner is a struct that represents an owner of repos.
  */
 pub struct Owner {
-    pub service: String,
+    pub service: Service,
     pub username: String,
 }
 
 impl Owner {
-    pub fn new(service: &str, username: &str) -> Owner {
+    pub fn new(service: Service, username: &str) -> Owner {
         Owner {
-            service: service.to_string(),
+            service,
             username: username.to_string(),
         }
     }
@@ -22,3 +35,57 @@ impl Owner {
         Author::from_owner(self, name)
     }
 }
+
+/**
+ * OwnerDetail is a struct that represents the response from the owner API.
+ * e.g. an organization or a user
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OwnerDetail {
+    pub service: Service,
+    pub username: String,
+    pub name: Option<String>,
+}
+
+impl OwnerDetail {
+    pub fn to_owner(&self) -> Owner {
+        Owner::new(self.service, &self.username)
+    }
+}
+
+/**
+ * Owner can be parsed from a slug such as gh/kitsuyui
+ * or from a Codecov URL such as https://app.codecov.io/gh/kitsuyui.
+ * A trailing repo name is ignored.
+ */
+impl FromStr for Owner {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Owner, Error> {
+        match slug_segments(s)[..] {
+            [service, username, ..] => Ok(Owner::new(service.parse()?, username)),
+            _ => Err(Error::ParseError(format!("expected service/owner: {}", s))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let owner = "gh/kitsuyui".parse::<Owner>().unwrap();
+        assert_eq!(owner.service, Service::GitHub);
+        assert_eq!(owner.username, "kitsuyui");
+
+        let owner = "https://app.codecov.io/gl/kitsuyui/rust-codecov"
+            .parse::<Owner>()
+            .unwrap();
+        assert_eq!(owner.service, Service::GitLab);
+        assert_eq!(owner.username, "kitsuyui");
+
+        assert!("gh".parse::<Owner>().is_err());
+        assert!("githb/kitsuyui".parse::<Owner>().is_err());
+    }
+}
@@ -0,0 +1,242 @@
+/**
+ * Codecov v2 API
+ * List endpoints return a page of results with links to the next and previous pages.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::url::Url;
+
+/**
+ * Paginated is a struct that represents a single page returned by a list endpoint.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Paginated<T> {
+    pub results: Vec<T>,
+    pub count: usize,
+    pub next: Option<Url>,
+    pub previous: Option<Url>,
+    pub total_pages: usize,
+}
+
+/**
+ * PageOptions controls how list endpoints are paginated.
+ * page_size is the number of items requested per page (the API default is used if None).
+ * max_items stops the iteration after the given number of items.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct PageOptions {
+    pub page_size: Option<usize>,
+    pub max_items: Option<usize>,
+}
+
+impl PageOptions {
+    pub fn new() -> PageOptions {
+        PageOptions::default()
+    }
+
+    pub fn page_size(mut self, page_size: usize) -> PageOptions {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn max_items(mut self, max_items: usize) -> PageOptions {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /**
+     * first_page_url appends page_size to the url of a list endpoint.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn first_page_url(&self, url: &str) -> String {
+        match self.page_size {
+            Some(page_size) => {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                format!("{}{}page_size={}", url, separator, page_size)
+            }
+            None => url.to_string(),
+        }
+    }
+}
+
+/**
+ * PageState holds the items of the current page and the url of the next page.
+ * It is shared by the blocking iterator and the async stream.
+ */
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) struct PageState<T> {
+    next_url: Option<Url>,
+    buffer: std::vec::IntoIter<T>,
+    remaining: Option<usize>,
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+impl<T> PageState<T> {
+    pub(crate) fn new(url: &str, options: &PageOptions) -> PageState<T> {
+        PageState {
+            next_url: Some(options.first_page_url(url)),
+            buffer: Vec::new().into_iter(),
+            remaining: options.max_items,
+        }
+    }
+
+    /**
+     * next_item returns the next buffered item, respecting max_items.
+     */
+    pub(crate) fn next_item(&mut self) -> Option<T> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        let item = self.buffer.next()?;
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        Some(item)
+    }
+
+    /**
+     * next_page_url returns the url to fetch when the buffer is exhausted.
+     * None means the iteration is over.
+     */
+    pub(crate) fn next_page_url(&mut self) -> Option<Url> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        self.next_url.take()
+    }
+
+    pub(crate) fn push_page(&mut self, page: Paginated<T>) {
+        self.next_url = page.next;
+        self.buffer = page.results.into_iter();
+    }
+}
+
+/**
+ * PageIter is a lazy iterator over the items of a list endpoint.
+ * A page is requested only when the items of the previous page are consumed.
+ * After an error is yielded, the iteration stops.
+ */
+#[cfg(feature = "blocking")]
+pub struct PageIter<'a, T> {
+    client: &'a crate::client::Client,
+    state: PageState<T>,
+}
+
+#[cfg(feature = "blocking")]
+impl<'a, T> PageIter<'a, T> {
+    pub(crate) fn new(
+        client: &'a crate::client::Client,
+        url: &str,
+        options: &PageOptions,
+    ) -> PageIter<'a, T> {
+        PageIter {
+            client,
+            state: PageState::new(url, options),
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T: serde::de::DeserializeOwned + std::fmt::Debug> Iterator for PageIter<'_, T> {
+    type Item = Result<T, crate::errors::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.state.next_item() {
+                return Some(Ok(item));
+            }
+            let url = self.state.next_page_url()?;
+            match self.client.api_request::<Paginated<T>>(&url) {
+                Ok(page) => self.state.push_page(page),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/**
+ * page_stream returns a lazy stream over the items of a list endpoint.
+ * A page is requested only when the items of the previous page are consumed.
+ * After an error is yielded, the stream ends.
+ */
+#[cfg(feature = "async")]
+pub(crate) fn page_stream<'a, T: serde::de::DeserializeOwned + std::fmt::Debug + 'a>(
+    client: &'a crate::async_client::AsyncClient,
+    url: &str,
+    options: &PageOptions,
+) -> impl futures_util::Stream<Item = Result<T, crate::errors::Error>> + use<'a, T> {
+    let state = PageState::new(url, options);
+    futures_util::stream::unfold(state, move |mut state| async move {
+        loop {
+            if let Some(item) = state.next_item() {
+                return Some((Ok(item), state));
+            }
+            let url = state.next_page_url()?;
+            match client.api_request::<Paginated<T>>(&url).await {
+                Ok(page) => state.push_page(page),
+                Err(e) => return Some((Err(e), state)),
+            }
+        }
+    })
+}
+
+#[cfg(all(test, any(feature = "blocking", feature = "async")))]
+mod tests {
+    use super::*;
+
+    fn page(results: Vec<u32>, next: Option<&str>) -> Paginated<u32> {
+        Paginated {
+            count: results.len(),
+            results,
+            next: next.map(|next| next.to_string()),
+            previous: None,
+            total_pages: 2,
+        }
+    }
+
+    #[test]
+    fn test_first_page_url() {
+        let url = "https://codecov.io/api/v2/github/codecov/repos";
+        assert_eq!(PageOptions::new().first_page_url(url), url);
+        assert_eq!(
+            PageOptions::new().page_size(100).first_page_url(url),
+            "https://codecov.io/api/v2/github/codecov/repos?page_size=100"
+        );
+        assert_eq!(
+            PageOptions::new()
+                .page_size(10)
+                .first_page_url("https://codecov.io/api/v2/github/codecov/repos?active=true"),
+            "https://codecov.io/api/v2/github/codecov/repos?active=true&page_size=10"
+        );
+    }
+
+    #[test]
+    fn test_page_state() {
+        let mut state = PageState::new("first", &PageOptions::new());
+        assert_eq!(state.next_item(), None);
+        assert_eq!(state.next_page_url(), Some("first".to_string()));
+        state.push_page(page(vec![1, 2], Some("second")));
+        assert_eq!(state.next_item(), Some(1));
+        assert_eq!(state.next_item(), Some(2));
+        assert_eq!(state.next_item(), None);
+        assert_eq!(state.next_page_url(), Some("second".to_string()));
+        state.push_page(page(vec![3], None));
+        assert_eq!(state.next_item(), Some(3));
+        assert_eq!(state.next_item(), None);
+        assert_eq!(state.next_page_url(), None);
+    }
+
+    #[test]
+    fn test_page_state_max_items() {
+        let mut state = PageState::new("first", &PageOptions::new().max_items(3));
+        assert_eq!(state.next_page_url(), Some("first".to_string()));
+        state.push_page(page(vec![1, 2], Some("second")));
+        assert_eq!(state.next_item(), Some(1));
+        assert_eq!(state.next_item(), Some(2));
+        assert_eq!(state.next_page_url(), Some("second".to_string()));
+        state.push_page(page(vec![3, 4], Some("third")));
+        assert_eq!(state.next_item(), Some(3));
+        assert_eq!(state.next_item(), None);
+        assert_eq!(state.next_page_url(), None);
+    }
+}
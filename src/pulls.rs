@@ -0,0 +1,121 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/pulls endpoint returns a list of pull requests for a given repo.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::commits::CommitAuthor;
+use crate::pagination::Paginated;
+use crate::timestamp::Timestamp;
+use crate::totals::Totals;
+
+/**
+ * PullsAPIResponse is a page of pull requests returned by the pulls API.
+ */
+pub type PullsAPIResponse = Paginated<Pull>;
+
+/**
+ * PullState is the state of a pull request.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PullState {
+    Open,
+    Merged,
+    Closed,
+}
+
+impl PullState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PullState::Open => "open",
+            PullState::Merged => "merged",
+            PullState::Closed => "closed",
+        }
+    }
+}
+
+/**
+ * Pull is a struct that represents a pull request.
+ * base_totals and head_totals are the totals of the base and head commits.
+ * patch is the coverage of the changed lines, when the API returns it.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Pull {
+    pub pullid: u64,
+    pub title: Option<String>,
+    pub base_totals: Option<Totals>,
+    pub head_totals: Option<Totals>,
+    pub updatestamp: Option<Timestamp>,
+    pub state: PullState,
+    pub ci_passed: Option<bool>,
+    pub author: Option<CommitAuthor>,
+    #[serde(default)]
+    pub patch: Option<Totals>,
+}
+
+impl Pull {
+    pub fn base_coverage(&self) -> Option<f64> {
+        self.base_totals.as_ref().and_then(|totals| totals.coverage)
+    }
+
+    pub fn head_coverage(&self) -> Option<f64> {
+        self.head_totals.as_ref().and_then(|totals| totals.coverage)
+    }
+
+    pub fn patch_coverage(&self) -> Option<f64> {
+        self.patch.as_ref().and_then(|totals| totals.coverage)
+    }
+
+    /**
+     * coverage_change returns head coverage - base coverage.
+     */
+    pub fn coverage_change(&self) -> Option<f64> {
+        Some(self.head_coverage()? - self.base_coverage()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_pulls() {
+        let j = json!({
+            "count": 1,
+            "next": null,
+            "previous": null,
+            "results": [
+                {
+                    "pullid": 42,
+                    "title": "Add compare endpoint",
+                    "base_totals": {"files": 2, "lines": 10, "hits": 8, "misses": 2, "partials": 0, "coverage": 80.0},
+                    "head_totals": {"files": 2, "lines": 12, "hits": 9, "misses": 3, "partials": 0, "coverage": 75.0},
+                    "updatestamp": "2023-08-01T19:10:56.045522Z",
+                    "state": "open",
+                    "ci_passed": true,
+                    "author": {"service": "github", "username": "kitsuyui", "name": "Yui Kitsu"}
+                },
+                {
+                    "pullid": 41,
+                    "title": null,
+                    "base_totals": null,
+                    "head_totals": null,
+                    "updatestamp": null,
+                    "state": "merged",
+                    "ci_passed": null,
+                    "author": null
+                }
+            ],
+            "total_pages": 1
+        });
+        let pulls = serde_json::from_value::<PullsAPIResponse>(j).unwrap();
+        assert_eq!(pulls.results[0].state, PullState::Open);
+        assert_eq!(pulls.results[0].coverage_change(), Some(-5.0));
+        assert_eq!(pulls.results[0].patch_coverage(), None);
+        assert_eq!(pulls.results[1].state, PullState::Merged);
+        assert_eq!(pulls.results[1].coverage_change(), None);
+    }
+}
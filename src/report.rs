@@ -0,0 +1,300 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/report endpoint returns the report of a commit, optionally filtered.
+ * The response is deserialized into branch_detail::Report.
+ * /repos/{repo}/report/tree endpoint returns the same report as a directory tree.
+ */
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::branch_detail::File;
+
+/**
+ * ReportTreeAPIResponse is the list of top-level nodes returned by the report tree API.
+ */
+pub type ReportTreeAPIResponse = Vec<ReportNode>;
+
+/**
+ * ReportQuery holds the filters of the report APIs.
+ * Without sha or branch, the report of the head of the default branch is returned.
+ * path is a path prefix. e.g. src/payments/
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReportQuery {
+    pub sha: Option<String>,
+    pub branch: Option<String>,
+    pub path: Option<String>,
+    pub flag: Option<String>,
+    pub component_id: Option<String>,
+}
+
+impl ReportQuery {
+    pub fn new() -> ReportQuery {
+        ReportQuery::default()
+    }
+
+    pub fn sha(mut self, sha: &str) -> ReportQuery {
+        self.sha = Some(sha.to_string());
+        self
+    }
+
+    pub fn branch(mut self, branch: &str) -> ReportQuery {
+        self.branch = Some(branch.to_string());
+        self
+    }
+
+    pub fn path(mut self, path: &str) -> ReportQuery {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    pub fn flag(mut self, flag: &str) -> ReportQuery {
+        self.flag = Some(flag.to_string());
+        self
+    }
+
+    pub fn component_id(mut self, component_id: &str) -> ReportQuery {
+        self.component_id = Some(component_id.to_string());
+        self
+    }
+
+    /**
+     * query returns the query parameters of the report API.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn query(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("sha", self.sha.clone()),
+            ("branch", self.branch.clone()),
+            ("path", self.path.clone()),
+            ("flag", self.flag.clone()),
+            ("component_id", self.component_id.clone()),
+        ]
+    }
+}
+
+/**
+ * ReportNode is a directory or a file in a report tree.
+ * A file has no children. Neither has a directory below the depth requested from the API.
+ * The counts of a directory are the sums over its files.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReportNode {
+    pub name: String,
+    pub full_path: String,
+    pub coverage: f64,
+    pub lines: usize,
+    pub hits: usize,
+    pub partials: usize,
+    pub misses: usize,
+    #[serde(default)]
+    pub children: Vec<ReportNode>,
+}
+
+/**
+ * build_report_tree builds a report tree from a flat list of files,
+ * e.g. the files of branch_detail::Report, by summing the totals per directory.
+ * Nodes are sorted by name at each level.
+ */
+pub fn build_report_tree(files: &[File]) -> Vec<ReportNode> {
+    let mut root = Directory::default();
+    for file in files {
+        let mut directory = &mut root;
+        let mut parts: Vec<&str> = file.name.split('/').filter(|p| !p.is_empty()).collect();
+        let Some(file_name) = parts.pop() else {
+            continue;
+        };
+        let mut full_path = String::new();
+        for part in parts {
+            if !full_path.is_empty() {
+                full_path.push('/');
+            }
+            full_path.push_str(part);
+            directory = directory
+                .directories
+                .entry(part.to_string())
+                .or_insert_with(|| Directory {
+                    full_path: full_path.clone(),
+                    ..Directory::default()
+                });
+        }
+        directory
+            .files
+            .insert(file_name.to_string(), file_node(file_name, file));
+    }
+    root.into_children()
+}
+
+#[derive(Default)]
+struct Directory {
+    full_path: String,
+    directories: BTreeMap<String, Directory>,
+    files: BTreeMap<String, ReportNode>,
+}
+
+impl Directory {
+    fn into_children(self) -> Vec<ReportNode> {
+        let mut children: Vec<ReportNode> = self
+            .directories
+            .into_iter()
+            .map(|(name, directory)| directory.into_node(name))
+            .chain(self.files.into_values())
+            .collect();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        children
+    }
+
+    fn into_node(self, name: String) -> ReportNode {
+        let full_path = self.full_path.clone();
+        let children = self.into_children();
+        let lines = children.iter().map(|child| child.lines).sum();
+        let hits = children.iter().map(|child| child.hits).sum();
+        ReportNode {
+            name,
+            full_path,
+            coverage: coverage_of(hits, lines),
+            lines,
+            hits,
+            partials: children.iter().map(|child| child.partials).sum(),
+            misses: children.iter().map(|child| child.misses).sum(),
+            children,
+        }
+    }
+}
+
+fn file_node(name: &str, file: &File) -> ReportNode {
+    let lines = file.totals.lines.unwrap_or(0);
+    let hits = file.totals.hits.unwrap_or(0);
+    ReportNode {
+        name: name.to_string(),
+        full_path: file.name.trim_matches('/').to_string(),
+        coverage: file.totals.coverage.unwrap_or(coverage_of(hits, lines)),
+        lines,
+        hits,
+        partials: file.totals.partials.unwrap_or(0),
+        misses: file.totals.misses.unwrap_or(0),
+        children: vec![],
+    }
+}
+
+/**
+ * coverage_of returns hits / lines as a percentage, as Codecov does.
+ */
+fn coverage_of(hits: usize, lines: usize) -> f64 {
+    if lines == 0 {
+        return 0.0;
+    }
+    hits as f64 / lines as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_query() {
+        let query = ReportQuery::new().branch("main").path("src/payments/");
+        assert_eq!(
+            query.query(),
+            vec![
+                ("sha", None),
+                ("branch", Some("main".to_string())),
+                ("path", Some("src/payments/".to_string())),
+                ("flag", None),
+                ("component_id", None),
+            ]
+        );
+        assert!(ReportQuery::new().query().iter().all(|(_, v)| v.is_none()));
+    }
+
+    fn file(name: &str, hits: usize, misses: usize, partials: usize) -> File {
+        let lines = hits + misses + partials;
+        serde_json::from_value(json!({
+            "name": name,
+            "totals": {
+                "files": 1,
+                "lines": lines,
+                "hits": hits,
+                "misses": misses,
+                "partials": partials,
+                "coverage": hits as f64 / lines as f64 * 100.0,
+                "branches": 0,
+                "methods": 0,
+                "sessions": 1,
+                "complexity": 0.0,
+                "complexity_total": 0.0,
+                "diff": 0
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_report_tree() {
+        let files = vec![
+            file("src/payments/refund.rs", 10, 0, 0),
+            file("src/payments/charge.rs", 5, 4, 1),
+            file("src/lib.rs", 3, 1, 0),
+            file("README.md", 0, 2, 0),
+        ];
+        let tree = build_report_tree(&files);
+        let names: Vec<&str> = tree.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, vec!["README.md", "src"]);
+        assert!(tree[0].children.is_empty());
+
+        let src = &tree[1];
+        assert_eq!(src.full_path, "src");
+        assert_eq!(
+            (src.lines, src.hits, src.misses, src.partials),
+            (24, 18, 5, 1)
+        );
+        assert_eq!(src.coverage, 75.0);
+        assert_eq!(src.children[0].name, "lib.rs");
+        assert_eq!(src.children[0].full_path, "src/lib.rs");
+
+        let payments = &src.children[1];
+        assert_eq!(payments.full_path, "src/payments");
+        assert_eq!(payments.lines, 20);
+        assert_eq!(payments.coverage, 75.0);
+        let names: Vec<&str> = payments
+            .children
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["charge.rs", "refund.rs"]);
+        assert_eq!(payments.children[0].coverage, 50.0);
+    }
+
+    #[test]
+    fn test_deserialize_report_tree() {
+        let j = json!([
+            {
+                "name": "src",
+                "full_path": "src",
+                "coverage": 75.0,
+                "lines": 4,
+                "hits": 3,
+                "partials": 0,
+                "misses": 1,
+                "children": [
+                    {
+                        "name": "lib.rs",
+                        "full_path": "src/lib.rs",
+                        "coverage": 75.0,
+                        "lines": 4,
+                        "hits": 3,
+                        "partials": 0,
+                        "misses": 1
+                    }
+                ]
+            }
+        ]);
+        let tree = serde_json::from_value::<ReportTreeAPIResponse>(j).unwrap();
+        assert_eq!(tree[0].children[0].full_path, "src/lib.rs");
+        assert!(tree[0].children[0].children.is_empty());
+    }
+}
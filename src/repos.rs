@@ -1,22 +1,20 @@
 use crate::author::Author;
-use crate::url::Url;
+use crate::pagination::Paginated;
 /**
  * Codecov v2 API
  * /repos endpoint returns a list of repos for a given owner.
+ * /repos/{repo} and /repos/{repo}/config endpoints return the detail and the config of a repo.
  */
 use serde::{Deserialize, Serialize};
 
+use crate::secret::Secret;
+use crate::timestamp::Timestamp;
+use crate::totals::Totals;
+
 /**
- * ReposAPIResponse is a struct that represents the response from the repos API.
+ * ReposAPIResponse is a page of repos returned by the repos API.
  */
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ReposAPIResponse {
-    pub results: Vec<Repo>,
-    pub count: usize,
-    pub next: Option<Url>,
-    pub previous: Option<Url>,
-    pub total_pages: usize,
-}
+pub type ReposAPIResponse = Paginated<Repo>;
 
 /**
  * Repo is a struct that represents a repo.
@@ -25,10 +23,76 @@ pub struct ReposAPIResponse {
 pub struct Repo {
     pub name: String,
     pub private: bool,
-    pub updatestamp: Option<String>, // TODO: ISO Date
+    pub updatestamp: Option<Timestamp>,
     pub author: Author,
     pub language: Option<String>,
     pub branch: Option<String>,
     pub active: bool,
     pub activated: bool,
 }
+
+/**
+ * RepoDetail is a struct that represents the response from the repo detail API.
+ * repo.branch is the default branch.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RepoDetail {
+    #[serde(flatten)]
+    pub repo: Repo,
+    pub totals: Option<Totals>,
+}
+
+impl RepoDetail {
+    pub fn default_branch(&self) -> Option<&str> {
+        self.repo.branch.as_deref()
+    }
+
+    /**
+     * Returns the latest coverage of the default branch.
+     */
+    pub fn latest_coverage(&self) -> Option<f64> {
+        self.totals.as_ref().and_then(|totals| totals.coverage)
+    }
+}
+
+/**
+ * RepoConfig is a struct that represents the response from the repo config API.
+ * The tokens are wrapped in Secret so that they are redacted in Debug output.
+ * Settings other than the tokens are kept as they are in settings.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RepoConfig {
+    pub upload_token: Secret,
+    #[serde(default)]
+    pub graph_token: Option<Secret>,
+    #[serde(flatten)]
+    pub settings: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_repo_config() {
+        let j = json!({
+            "upload_token": "f1b5b4b6-0000-4b4b-8b8b-000000000000",
+            "graph_token": "GRAPHTOKEN",
+            "bot": "codecov"
+        });
+        let config = serde_json::from_value::<RepoConfig>(j).unwrap();
+        assert_eq!(
+            config.upload_token.expose(),
+            "f1b5b4b6-0000-4b4b-8b8b-000000000000"
+        );
+        assert_eq!(config.settings.get("bot"), Some(&json!("codecov")));
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("f1b5b4b6"));
+        assert!(!debug.contains("GRAPHTOKEN"));
+        let serialized = serde_json::to_string(&config).unwrap();
+        assert!(!serialized.contains("f1b5b4b6"));
+        assert!(!serialized.contains("GRAPHTOKEN"));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::errors::Error;
+
+/**
+ * RetryPolicy decides whether a failed request is retried and how long to wait before it.
+ * The delay grows exponentially from backoff_base (base, base * 2, base * 4, ...)
+ * up to max_backoff, plus a random jitter in [0, jitter).
+ * When the API returns a Retry-After header (429), it is used instead of the backoff.
+ * A Retry-After longer than max_backoff is not waited for: the request is not retried
+ * and the Error::RateLimited is returned to the caller.
+ */
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /**
+     * Total number of attempts including the first request. 1 disables retries.
+     */
+    pub max_attempts: u32,
+    pub backoff_base: Duration,
+    pub max_backoff: Duration,
+    pub jitter: Duration,
+    pub retryable_statuses: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff_base: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: Duration::from_millis(250),
+            retryable_statuses: vec![429, 500, 502, 503, 504],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /**
+     * none returns a policy that never retries.
+     */
+    pub fn none() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /**
+     * should_retry returns true if the request failed with error on the given attempt (1-based)
+     * should be made again.
+     * Connection errors and timeouts are retried as well as the retryable statuses.
+     */
+    pub fn should_retry(&self, error: &Error, attempt: u32) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+        match error {
+            Error::ReqwestError(e) => e.is_timeout() || e.is_connect(),
+            Error::RateLimited {
+                retry_after: Some(retry_after),
+            } if *retry_after > self.max_backoff => false,
+            _ => match error.status() {
+                Some(status) => self.retryable_statuses.contains(&status),
+                None => false,
+            },
+        }
+    }
+
+    /**
+     * delay returns how long to wait before the next attempt after the given attempt (1-based) failed.
+     * It never exceeds max_backoff plus jitter.
+     */
+    pub fn delay(&self, error: &Error, attempt: u32) -> Duration {
+        if let Error::RateLimited {
+            retry_after: Some(retry_after),
+        } = error
+        {
+            return (*retry_after).min(self.max_backoff);
+        }
+        self.backoff(attempt) + self.random_jitter()
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self.backoff_base.saturating_mul(1 << exponent);
+        backoff.min(self.max_backoff)
+    }
+
+    fn random_jitter(&self) -> Duration {
+        let max = self.jitter.as_nanos() as u64;
+        if max == 0 {
+            return Duration::ZERO;
+        }
+        // RandomState is seeded randomly, which is enough for jitter.
+        let random = RandomState::new().build_hasher().finish();
+        Duration::from_nanos(random % max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error() -> Error {
+        Error::Server {
+            status: 502,
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry(&server_error(), 1));
+        assert!(policy.should_retry(&server_error(), 2));
+        assert!(!policy.should_retry(&server_error(), 3));
+        assert!(policy.should_retry(&Error::RateLimited { retry_after: None }, 1));
+        assert!(!policy.should_retry(&Error::Unauthorized, 1));
+        assert!(!policy.should_retry(
+            &Error::NotFound {
+                detail: String::new()
+            },
+            1
+        ));
+        assert!(!RetryPolicy::none().should_retry(&server_error(), 1));
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy {
+            backoff_base: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+            jitter: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.delay(&server_error(), 1), Duration::from_millis(100));
+        assert_eq!(policy.delay(&server_error(), 2), Duration::from_millis(200));
+        assert_eq!(policy.delay(&server_error(), 3), Duration::from_millis(350));
+        assert_eq!(
+            policy.delay(&server_error(), 100),
+            Duration::from_millis(350)
+        );
+    }
+
+    #[test]
+    fn test_delay_with_jitter() {
+        let policy = RetryPolicy {
+            backoff_base: Duration::from_millis(100),
+            jitter: Duration::from_millis(50),
+            ..RetryPolicy::default()
+        };
+        let delay = policy.delay(&server_error(), 1);
+        assert!(delay >= Duration::from_millis(100));
+        assert!(delay < Duration::from_millis(150));
+    }
+
+    #[test]
+    fn test_delay_honors_retry_after() {
+        let policy = RetryPolicy::default();
+        let error = Error::RateLimited {
+            retry_after: Some(Duration::from_secs(7)),
+        };
+        assert_eq!(policy.delay(&error, 1), Duration::from_secs(7));
+        assert!(policy.should_retry(&error, 1));
+    }
+
+    #[test]
+    fn test_retry_after_above_max_backoff() {
+        let policy = RetryPolicy {
+            max_backoff: Duration::from_secs(10),
+            ..RetryPolicy::default()
+        };
+        let error = Error::RateLimited {
+            retry_after: Some(Duration::from_secs(3600)),
+        };
+        assert!(!policy.should_retry(&error, 1));
+        assert_eq!(policy.delay(&error, 1), Duration::from_secs(10));
+    }
+}
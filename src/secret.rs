@@ -0,0 +1,78 @@
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const REDACTED: &str = "[REDACTED]";
+
+/**
+ * Secret holds a sensitive value such as an upload token.
+ * Debug, Display and Serialize write `[REDACTED]` so that the value does not end up in logs or files.
+ * Deserialize rejects `[REDACTED]` so that a serialized Secret is never read back as the token.
+ * Call expose to get the value.
+ */
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: &str) -> Secret {
+        Secret(value.to_string())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({})", REDACTED)
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Secret, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        if value == REDACTED {
+            return Err(serde::de::Error::custom(
+                "the value was redacted when it was serialized",
+            ));
+        }
+        Ok(Secret(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted() {
+        let secret = Secret::new("f1b5b4b6-0000-4b4b-8b8b-000000000000");
+        assert_eq!(format!("{:?}", secret), "Secret([REDACTED])");
+        assert_eq!(secret.to_string(), "[REDACTED]");
+        assert_eq!(secret.expose(), "f1b5b4b6-0000-4b4b-8b8b-000000000000");
+        assert_eq!(
+            serde_json::from_str::<Secret>(r#""abc""#).unwrap(),
+            Secret::new("abc")
+        );
+        assert_eq!(serde_json::to_string(&secret).unwrap(), r#""[REDACTED]""#);
+    }
+
+    #[test]
+    fn test_redacted_is_not_deserialized() {
+        let content = serde_json::to_string(&Secret::new("abc")).unwrap();
+        assert!(serde_json::from_str::<Secret>(&content).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+
+/**
+ * Service is a git hosting service supported by Codecov.
+ * It is serialized as the name used in the API paths. e.g. github
+ * FromStr also accepts the short names used in Codecov URLs. e.g. gh
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Service {
+    #[serde(rename = "github")]
+    GitHub,
+    #[serde(rename = "gitlab")]
+    GitLab,
+    #[serde(rename = "bitbucket")]
+    Bitbucket,
+    #[serde(rename = "github_enterprise")]
+    GitHubEnterprise,
+    #[serde(rename = "gitlab_enterprise")]
+    GitLabEnterprise,
+    #[serde(rename = "bitbucket_server")]
+    BitbucketServer,
+}
+
+impl Service {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Service::GitHub => "github",
+            Service::GitLab => "gitlab",
+            Service::Bitbucket => "bitbucket",
+            Service::GitHubEnterprise => "github_enterprise",
+            Service::GitLabEnterprise => "gitlab_enterprise",
+            Service::BitbucketServer => "bitbucket_server",
+        }
+    }
+
+    /**
+     * short_name returns the name used in Codecov URLs. e.g. https://app.codecov.io/gh/...
+     */
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            Service::GitHub => "gh",
+            Service::GitLab => "gl",
+            Service::Bitbucket => "bb",
+            Service::GitHubEnterprise => "ghe",
+            Service::GitLabEnterprise => "gle",
+            Service::BitbucketServer => "bbs",
+        }
+    }
+}
+
+impl fmt::Display for Service {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Service {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Service, Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "github" | "gh" => Ok(Service::GitHub),
+            "gitlab" | "gl" => Ok(Service::GitLab),
+            "bitbucket" | "bb" => Ok(Service::Bitbucket),
+            "github_enterprise" | "ghe" => Ok(Service::GitHubEnterprise),
+            "gitlab_enterprise" | "gle" => Ok(Service::GitLabEnterprise),
+            "bitbucket_server" | "bbs" => Ok(Service::BitbucketServer),
+            _ => Err(Error::ParseError(format!("unknown service: {}", s))),
+        }
+    }
+}
+
+/**
+ * slug_segments returns the path segments of a slug such as gh/kitsuyui/rust-codecov,
+ * or of a Codecov URL such as https://app.codecov.io/gh/kitsuyui/rust-codecov/tree/main.
+ */
+pub(crate) fn slug_segments(s: &str) -> Vec<&str> {
+    let path = match s.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+        None => s,
+    };
+    let path = path.split(['?', '#']).next().unwrap_or("");
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("github".parse::<Service>().unwrap(), Service::GitHub);
+        assert_eq!("gh".parse::<Service>().unwrap(), Service::GitHub);
+        assert_eq!("GL".parse::<Service>().unwrap(), Service::GitLab);
+        assert_eq!("bbs".parse::<Service>().unwrap(), Service::BitbucketServer);
+        assert!("githb".parse::<Service>().is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(
+            serde_json::to_value(Service::GitHubEnterprise).unwrap(),
+            json!("github_enterprise")
+        );
+        assert_eq!(
+            serde_json::from_value::<Service>(json!("bitbucket")).unwrap(),
+            Service::Bitbucket
+        );
+        assert!(serde_json::from_value::<Service>(json!("githb")).is_err());
+    }
+
+    #[test]
+    fn test_slug_segments() {
+        assert_eq!(
+            slug_segments("gh/kitsuyui/rust-codecov"),
+            vec!["gh", "kitsuyui", "rust-codecov"]
+        );
+        assert_eq!(
+            slug_segments("https://app.codecov.io/gh/kitsuyui/rust-codecov/tree/main?branch=main"),
+            vec!["gh", "kitsuyui", "rust-codecov", "tree", "main"]
+        );
+        assert!(slug_segments("https://app.codecov.io").is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/test-results endpoint returns the test analytics collected from JUnit uploads.
+ * https://docs.codecov.com/docs/test-analytics
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::coverage::Interval;
+use crate::pagination::Paginated;
+
+/**
+ * TestResultsAPIResponse is a page of tests returned by the test results API.
+ */
+pub type TestResultsAPIResponse = Paginated<TestResult>;
+
+/**
+ * TestResult is the aggregated result of a test over the requested interval.
+ * failure_rate is between 0.0 and 1.0. avg_duration is in seconds.
+ * last_run_commit is the sha of the commit the test last ran on.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TestResult {
+    pub name: String,
+    #[serde(default)]
+    pub testsuite: Option<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub failure_rate: Option<f64>,
+    #[serde(default)]
+    pub avg_duration: Option<f64>,
+    #[serde(default)]
+    pub last_run_commit: Option<String>,
+    #[serde(default)]
+    pub total_fail_count: Option<usize>,
+    #[serde(default)]
+    pub total_pass_count: Option<usize>,
+    #[serde(default)]
+    pub total_skip_count: Option<usize>,
+}
+
+impl TestResult {
+    /**
+     * Returns true if the test failed at least once in the interval.
+     */
+    pub fn has_failures(&self) -> bool {
+        self.failure_rate.is_some_and(|rate| rate > 0.0)
+            || self.total_fail_count.is_some_and(|count| count > 0)
+    }
+}
+
+/**
+ * TestResultsQuery holds the filters of the test results API.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestResultsQuery {
+    pub branch: Option<String>,
+    pub interval: Option<Interval>,
+}
+
+impl TestResultsQuery {
+    pub fn new() -> TestResultsQuery {
+        TestResultsQuery::default()
+    }
+
+    pub fn branch(mut self, branch: &str) -> TestResultsQuery {
+        self.branch = Some(branch.to_string());
+        self
+    }
+
+    pub fn interval(mut self, interval: Interval) -> TestResultsQuery {
+        self.interval = Some(interval);
+        self
+    }
+
+    /**
+     * query returns the query parameters of the test results API.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn query(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("branch", self.branch.clone()),
+            (
+                "interval",
+                self.interval.map(|interval| interval.as_str().to_string()),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_test_results() {
+        let j = json!({
+            "count": 2,
+            "next": null,
+            "previous": null,
+            "results": [
+                {
+                    "name": "tests::test_parse",
+                    "testsuite": "codecov",
+                    "flags": ["unit"],
+                    "failure_rate": 0.25,
+                    "avg_duration": 0.012,
+                    "last_run_commit": "1eb341765e7c3daa88ae5d2a751538a620c6dbce",
+                    "total_fail_count": 1,
+                    "total_pass_count": 3,
+                    "total_skip_count": 0
+                },
+                {"name": "tests::test_ok", "failure_rate": 0.0}
+            ],
+            "total_pages": 1
+        });
+        let tests = serde_json::from_value::<TestResultsAPIResponse>(j).unwrap();
+        assert!(tests.results[0].has_failures());
+        assert_eq!(tests.results[0].flags, vec!["unit"]);
+        assert!(!tests.results[1].has_failures());
+        assert!(tests.results[1].testsuite.is_none());
+    }
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_query() {
+        assert_eq!(
+            TestResultsQuery::new()
+                .branch("main")
+                .interval(Interval::ThirtyDays)
+                .query(),
+            vec![
+                ("branch", Some("main".to_string())),
+                ("interval", Some("30d".to_string())),
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::Error;
+
+/**
+ * Timestamp is a date returned by the API in ISO 8601. e.g. 2023-08-01T19:10:56.045522Z
+ * It keeps the string as returned (as_str, Display, Serialize).
+ * With the chrono feature, the string is parsed once when the Timestamp is created:
+ * an invalid date fails deserialization, datetime returns the parsed value and
+ * timestamps are ordered by the instant they represent.
+ * Without it, timestamps are ordered by their strings.
+ */
+#[derive(Debug, Clone)]
+pub struct Timestamp {
+    value: String,
+    #[cfg(feature = "chrono")]
+    datetime: chrono::DateTime<chrono::Utc>,
+}
+
+impl Timestamp {
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /**
+     * datetime returns the timestamp as a chrono::DateTime in UTC.
+     */
+    #[cfg(feature = "chrono")]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        self.datetime
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Timestamp, Error> {
+        #[cfg(feature = "chrono")]
+        let datetime = match chrono::DateTime::parse_from_rfc3339(value) {
+            Ok(datetime) => datetime.with_timezone(&chrono::Utc),
+            Err(e) => {
+                return Err(Error::ParseError(format!(
+                    "invalid timestamp {}: {}",
+                    value, e
+                )));
+            }
+        };
+        Ok(Timestamp {
+            value: value.to_string(),
+            #[cfg(feature = "chrono")]
+            datetime,
+        })
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.value)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Timestamp) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Timestamp {}
+
+impl Hash for Timestamp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+/**
+ * Timestamps of the same instant in different formats are ordered by their strings
+ * to stay consistent with Eq.
+ */
+impl Ord for Timestamp {
+    #[cfg(feature = "chrono")]
+    fn cmp(&self, other: &Timestamp) -> Ordering {
+        (self.datetime, &self.value).cmp(&(other.datetime, &other.value))
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    fn cmp(&self, other: &Timestamp) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Timestamp) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Timestamp {
+        Timestamp {
+            value: datetime.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            datetime,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn timestamp(value: &str) -> Timestamp {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_deserialize_timestamp() {
+        let timestamp =
+            serde_json::from_value::<Timestamp>(json!("2023-08-01T19:10:56.045522Z")).unwrap();
+        assert_eq!(timestamp.as_str(), "2023-08-01T19:10:56.045522Z");
+        assert_eq!(timestamp.to_string(), "2023-08-01T19:10:56.045522Z");
+        assert_eq!(
+            serde_json::to_value(&timestamp).unwrap(),
+            json!("2023-08-01T19:10:56.045522Z")
+        );
+    }
+
+    #[test]
+    fn test_ord() {
+        let first = timestamp("2023-08-01T15:41:47.045522Z");
+        let second = timestamp("2023-08-02T00:00:00.000000Z");
+        assert!(first < second);
+        assert_eq!(first.cmp(&first.clone()), Ordering::Equal);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_ord_by_instant() {
+        let seconds = timestamp("2023-08-01T15:41:47Z");
+        let micros = timestamp("2023-08-01T15:41:47.045522Z");
+        let offset = timestamp("2023-08-01T17:41:46+02:00");
+        let next_day = timestamp("2023-08-02T00:00:00Z");
+        let mut timestamps = vec![
+            next_day.clone(),
+            micros.clone(),
+            seconds.clone(),
+            offset.clone(),
+        ];
+        timestamps.sort();
+        assert_eq!(timestamps, vec![offset, seconds, micros, next_day]);
+        assert_eq!(
+            timestamp("2023-08-01T15:41:47+00:00").cmp(&timestamp("2023-08-01T15:41:47Z")),
+            Ordering::Less
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_datetime() {
+        use chrono::{TimeZone, Timelike, Utc};
+
+        let seconds = timestamp("2023-08-01T15:41:47Z").datetime();
+        assert_eq!(
+            seconds,
+            Utc.with_ymd_and_hms(2023, 8, 1, 15, 41, 47).unwrap()
+        );
+        let micros = timestamp("2023-08-01T19:10:56.045522Z").datetime();
+        assert_eq!(micros.nanosecond(), 45_522_000);
+        assert!("yesterday".parse::<Timestamp>().is_err());
+        assert!(serde_json::from_value::<Timestamp>(json!("yesterday")).is_err());
+        assert_eq!(Timestamp::from(seconds).as_str(), "2023-08-01T15:41:47Z");
+    }
+}
@@ -24,8 +24,9 @@ pub struct Totals {
 
 /**
  * Diff is a struct that represents the diff for a commit.
- * Diff may be a u64 or an array of Option<String> like this:
+ * Diff may be a u64 or a positional array of the totals of the changed lines like this:
  * "diff": [0, 0, 0, 0, "81.81818", null, 0, 0, 0, 0, "84.5", null, 0]
+ * Use Diff::totals to decode the array into DiffTotals.
  */
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
@@ -38,5 +39,141 @@ pub enum Diff {
 #[serde(untagged)]
 pub enum DiffValue {
     NumValue(u64),
+    FloatValue(f64),
     StringValue(String),
 }
+
+impl DiffValue {
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            DiffValue::NumValue(value) => Some(*value),
+            DiffValue::FloatValue(_) => None,
+            DiffValue::StringValue(value) => value.parse().ok(),
+        }
+    }
+
+    /**
+     * as_f64 returns the value as f64. A string such as "81.81818" is parsed.
+     */
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DiffValue::NumValue(value) => Some(*value as f64),
+            DiffValue::FloatValue(value) => Some(*value),
+            DiffValue::StringValue(value) => value.parse().ok(),
+        }
+    }
+}
+
+/**
+ * DiffTotals is the totals of the changed lines decoded from Diff::Array.
+ * The positions are the same as the ReportTotals of Codecov:
+ * files, lines, hits, misses, partials, coverage, branches, methods, messages, sessions,
+ * complexity, complexity_total, diff
+ */
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct DiffTotals {
+    pub files: Option<u64>,
+    pub lines: Option<u64>,
+    pub hits: Option<u64>,
+    pub misses: Option<u64>,
+    pub partials: Option<u64>,
+    pub coverage: Option<f64>,
+    pub branches: Option<u64>,
+    pub methods: Option<u64>,
+    pub messages: Option<u64>,
+    pub sessions: Option<u64>,
+    pub complexity: Option<f64>,
+    pub complexity_total: Option<f64>,
+    pub diff: Option<u64>,
+}
+
+impl Diff {
+    /**
+     * totals decodes Diff::Array into DiffTotals.
+     * Diff::Value has no breakdown, so None is returned for it.
+     */
+    pub fn totals(&self) -> Option<DiffTotals> {
+        let values = match self {
+            Diff::Value(_) => return None,
+            Diff::Array(values) => values,
+        };
+        let at = |index: usize| values.get(index).and_then(|value| value.as_ref());
+        let int = |index: usize| at(index).and_then(DiffValue::as_u64);
+        let float = |index: usize| at(index).and_then(DiffValue::as_f64);
+        Some(DiffTotals {
+            files: int(0),
+            lines: int(1),
+            hits: int(2),
+            misses: int(3),
+            partials: int(4),
+            coverage: float(5),
+            branches: int(6),
+            methods: int(7),
+            messages: int(8),
+            sessions: int(9),
+            complexity: float(10),
+            complexity_total: float(11),
+            diff: int(12),
+        })
+    }
+}
+
+impl Totals {
+    /**
+     * diff_totals returns the decoded totals of the changed lines if the API returned them.
+     */
+    pub fn diff_totals(&self) -> Option<DiffTotals> {
+        self.diff.as_ref().and_then(Diff::totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_totals() {
+        let diff = serde_json::from_value::<Diff>(json!([
+            1, 11, 9, 2, 0, "81.81818", 0, 0, 0, 1, null, null, 0
+        ]))
+        .unwrap();
+        let totals = diff.totals().unwrap();
+        assert_eq!(totals.files, Some(1));
+        assert_eq!(totals.lines, Some(11));
+        assert_eq!(totals.hits, Some(9));
+        assert_eq!(totals.misses, Some(2));
+        assert_eq!(totals.coverage, Some(81.81818));
+        assert_eq!(totals.sessions, Some(1));
+        assert_eq!(totals.complexity, None);
+
+        let short = serde_json::from_value::<Diff>(json!([0, 2, 1.5])).unwrap();
+        assert_eq!(short.totals().unwrap().hits, None);
+        assert_eq!(short.totals().unwrap().coverage, None);
+        assert!(Diff::Value(0).totals().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_totals() {
+        let totals = serde_json::from_value::<Totals>(json!({
+            "files": 1,
+            "lines": 11,
+            "hits": 9,
+            "misses": 2,
+            "partials": 0,
+            "coverage": 81.81,
+            "branches": 0,
+            "methods": 0,
+            "sessions": 1,
+            "complexity": 0.0,
+            "complexity_total": 0.0,
+            "diff": [0, 2, 2, 0, 0, "100", 0, 0, 0, 0, null, null, 0]
+        }))
+        .unwrap();
+        assert_eq!(totals.diff_totals().unwrap().coverage, Some(100.0));
+        let totals =
+            serde_json::from_value::<Totals>(json!({"coverage": 80.0, "diff": 0})).unwrap();
+        assert!(totals.diff_totals().is_none());
+    }
+}
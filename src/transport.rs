@@ -0,0 +1,477 @@
+/**
+ * HttpTransport sits between the clients and the HTTP library.
+ * ReqwestTransport is used by default. MockTransport serves canned responses for offline tests.
+ */
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+
+/**
+ * Method is the HTTP method of a request.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/**
+ * HttpRequest is a request made by the clients.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    pub fn new(method: Method, url: &str) -> HttpRequest {
+        HttpRequest {
+            method,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn get(url: &str) -> HttpRequest {
+        HttpRequest::new(Method::Get, url)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> HttpRequest {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /**
+     * json sets the body to the given JSON value along with the Content-Type header.
+     */
+    pub fn json(self, value: &serde_json::Value) -> HttpRequest {
+        let mut request = self.header("Content-Type", "application/json");
+        request.body = Some(value.to_string());
+        request
+    }
+
+    /**
+     * header_value returns the value of the first header with the given name (case-insensitive).
+     */
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+/**
+ * HttpResponse is a response returned by a transport.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: &str) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    /**
+     * json returns a 200 response with the given JSON body.
+     */
+    pub fn json(value: &serde_json::Value) -> HttpResponse {
+        HttpResponse::new(200, &value.to_string()).header("Content-Type", "application/json")
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> HttpResponse {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /**
+     * header_value returns the value of the first header with the given name (case-insensitive).
+     */
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /**
+     * into_json returns the body as JSON, or a typed Error for a non-success status.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn into_json(self) -> Result<serde_json::Value, Error> {
+        if !self.is_success() {
+            let retry_after = self.header_value("Retry-After").map(|v| v.to_string());
+            return Err(Error::from_status(
+                self.status,
+                retry_after.as_deref(),
+                self.body,
+            ));
+        }
+        match serde_json::from_str::<serde_json::Value>(&self.body) {
+            Ok(value) => Ok(value),
+            Err(e) => Err(Error::DeserializeError(e)),
+        }
+    }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/**
+ * HttpTransport sends a request and returns the response.
+ * A non-success status is not an error at this level.
+ */
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error>;
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        (**self).send(request)
+    }
+}
+
+/**
+ * BoxFuture is the future returned by AsyncHttpTransport.
+ */
+#[cfg(feature = "async")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/**
+ * AsyncHttpTransport is the asynchronous version of HttpTransport.
+ */
+#[cfg(feature = "async")]
+pub trait AsyncHttpTransport: Send + Sync {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse, Error>>;
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncHttpTransport + ?Sized> AsyncHttpTransport for Arc<T> {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        (**self).send(request)
+    }
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn reqwest_method(method: Method) -> reqwest::Method {
+    match method {
+        Method::Get => reqwest::Method::GET,
+        Method::Post => reqwest::Method::POST,
+        Method::Put => reqwest::Method::PUT,
+        Method::Patch => reqwest::Method::PATCH,
+        Method::Delete => reqwest::Method::DELETE,
+    }
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn response_headers(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| match value.to_str() {
+            Ok(value) => Some((name.to_string(), value.to_string())),
+            Err(_) => None,
+        })
+        .collect()
+}
+
+/**
+ * ReqwestTransport is the default transport of Client using reqwest::blocking.
+ */
+#[cfg(feature = "blocking")]
+#[derive(Default)]
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "blocking")]
+impl ReqwestTransport {
+    pub fn new() -> ReqwestTransport {
+        ReqwestTransport::default()
+    }
+
+    pub fn with_client(client: reqwest::blocking::Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        let mut req = self
+            .client
+            .request(reqwest_method(request.method), &request.url);
+        for (name, value) in &request.headers {
+            req = req.header(name, value);
+        }
+        if let Some(body) = &request.body {
+            req = req.body(body.to_string());
+        }
+        let res = match req.send() {
+            Ok(res) => res,
+            Err(e) => return Err(Error::ReqwestError(e)),
+        };
+        let status = res.status().as_u16();
+        let headers = response_headers(res.headers());
+        let body = match res.text() {
+            Ok(body) => body,
+            Err(e) => return Err(Error::ReqwestError(e)),
+        };
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/**
+ * AsyncReqwestTransport is the default transport of AsyncClient using reqwest.
+ */
+#[cfg(feature = "async")]
+#[derive(Default)]
+pub struct AsyncReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "async")]
+impl AsyncReqwestTransport {
+    pub fn new() -> AsyncReqwestTransport {
+        AsyncReqwestTransport::default()
+    }
+
+    pub fn with_client(client: reqwest::Client) -> AsyncReqwestTransport {
+        AsyncReqwestTransport { client }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncHttpTransport for AsyncReqwestTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        Box::pin(async move {
+            let mut req = self
+                .client
+                .request(reqwest_method(request.method), &request.url);
+            for (name, value) in &request.headers {
+                req = req.header(name, value);
+            }
+            if let Some(body) = &request.body {
+                req = req.body(body.to_string());
+            }
+            let res = match req.send().await {
+                Ok(res) => res,
+                Err(e) => return Err(Error::ReqwestError(e)),
+            };
+            let status = res.status().as_u16();
+            let headers = response_headers(res.headers());
+            let body = match res.text().await {
+                Ok(body) => body,
+                Err(e) => return Err(Error::ReqwestError(e)),
+            };
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/**
+ * MockTransport maps method + URL to canned responses. It never touches the network.
+ * When several responses are registered for the same request, they are returned in order
+ * and the last one is repeated.
+ * A request without a registered response gets a 404.
+ * Every request is recorded and can be inspected with requests().
+ */
+#[derive(Default)]
+pub struct MockTransport {
+    responses: Mutex<HashMap<(Method, String), VecDeque<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> MockTransport {
+        MockTransport::default()
+    }
+
+    /**
+     * with_response registers a response for method + url.
+     */
+    pub fn with_response(self, method: Method, url: &str, response: HttpResponse) -> MockTransport {
+        self.push_response(method, url, response);
+        self
+    }
+
+    /**
+     * with_json registers a 200 response with the given JSON body for GET url.
+     */
+    pub fn with_json(self, url: &str, value: serde_json::Value) -> MockTransport {
+        self.with_response(Method::Get, url, HttpResponse::json(&value))
+    }
+
+    pub fn push_response(&self, method: Method, url: &str, response: HttpResponse) {
+        if let Ok(mut responses) = self.responses.lock() {
+            responses
+                .entry((method, url.to_string()))
+                .or_default()
+                .push_back(response);
+        }
+    }
+
+    /**
+     * requests returns all requests sent to this transport.
+     */
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        match self.requests.lock() {
+            Ok(requests) => requests.clone(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn respond(&self, request: &HttpRequest) -> HttpResponse {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(request.clone());
+        }
+        let not_found = || {
+            let detail = format!("no mock response for {} {}", request.method, request.url);
+            HttpResponse::new(404, &serde_json::json!({ "detail": detail }).to_string())
+        };
+        let mut responses = match self.responses.lock() {
+            Ok(responses) => responses,
+            Err(_) => return not_found(),
+        };
+        match responses.get_mut(&(request.method, request.url.to_string())) {
+            Some(queue) if queue.len() > 1 => queue.pop_front().unwrap_or_else(not_found),
+            Some(queue) => queue.front().cloned().unwrap_or_else(not_found),
+            None => not_found(),
+        }
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        Ok(self.respond(request))
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncHttpTransport for MockTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        Box::pin(async move { Ok(self.respond(request)) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_mock_transport() {
+        let url = "https://codecov.io/api/v2/github/codecov";
+        let mock = MockTransport::new()
+            .with_response(Method::Get, url, HttpResponse::new(502, "Bad Gateway"))
+            .with_json(url, json!({"name": "codecov"}));
+        let request = HttpRequest::get(url).header("Authorization", "bearer token");
+
+        let response = HttpTransport::send(&mock, &request).unwrap();
+        assert_eq!(response.status, 502);
+        let response = HttpTransport::send(&mock, &request).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.header_value("content-type"),
+            Some("application/json")
+        );
+        let response = HttpTransport::send(&mock, &request).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&response.body).unwrap(),
+            json!({"name": "codecov"})
+        );
+
+        let response = HttpTransport::send(&mock, &HttpRequest::new(Method::Patch, url)).unwrap();
+        assert_eq!(response.status, 404);
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(
+            requests[0].header_value("authorization"),
+            Some("bearer token")
+        );
+    }
+
+    #[test]
+    fn test_json_request() {
+        let request = HttpRequest::new(
+            Method::Patch,
+            "https://codecov.io/api/v2/github/codecov/users/kitsuyui",
+        )
+        .json(&serde_json::json!({"activated": true}));
+        assert_eq!(
+            request.header_value("content-type"),
+            Some("application/json")
+        );
+        assert_eq!(request.body.as_deref(), Some(r#"{"activated":true}"#));
+    }
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_into_json() {
+        let response = HttpResponse::new(429, "").header("Retry-After", "3");
+        match response.into_json() {
+            Err(Error::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(std::time::Duration::from_secs(3)))
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+        let response = HttpResponse::new(200, "not json");
+        assert!(matches!(
+            response.into_json(),
+            Err(Error::DeserializeError(_))
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+/**
+ * Codecov v2 API
+ * /repos/{repo}/commits/{sha}/uploads endpoint returns the uploads (sessions) of a commit.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::pagination::Paginated;
+use crate::timestamp::Timestamp;
+use crate::totals::Totals;
+
+/**
+ * UploadsAPIResponse is a page of uploads returned by the uploads API.
+ */
+pub type UploadsAPIResponse = Paginated<Upload>;
+
+/**
+ * Upload is a struct that represents a coverage upload from CI.
+ * state is e.g. uploaded, processed or error.
+ * totals is None until the upload is processed.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Upload {
+    #[serde(default)]
+    pub id: Option<u64>,
+    pub created_at: Option<Timestamp>,
+    #[serde(default)]
+    pub updated_at: Option<Timestamp>,
+    pub state: Option<String>,
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub upload_type: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub build_code: Option<String>,
+    #[serde(default)]
+    pub build_url: Option<String>,
+    #[serde(default)]
+    pub job_code: Option<String>,
+    #[serde(default)]
+    pub ci_url: Option<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub totals: Option<Totals>,
+}
+
+impl Upload {
+    pub fn is_error(&self) -> bool {
+        self.state.as_deref() == Some("error")
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_uploads() {
+        let j = json!({
+            "count": 2,
+            "next": null,
+            "previous": null,
+            "results": [
+                {
+                    "id": 1,
+                    "created_at": "2023-08-01T15:42:00Z",
+                    "updated_at": "2023-08-01T15:43:00Z",
+                    "state": "processed",
+                    "provider": "github-actions",
+                    "upload_type": "uploaded",
+                    "build_code": "5735069843",
+                    "build_url": "https://github.com/codecov/codecov-demo/actions/runs/5735069843",
+                    "job_code": null,
+                    "flags": ["unit"],
+                    "totals": {"files": 3, "lines": 30, "hits": 27, "misses": 3, "partials": 0, "coverage": 90.0}
+                },
+                {
+                    "created_at": "2023-08-01T15:44:00Z",
+                    "state": "error",
+                    "provider": "circleci",
+                    "totals": null
+                }
+            ],
+            "total_pages": 1
+        });
+        let uploads = serde_json::from_value::<UploadsAPIResponse>(j).unwrap();
+        assert!(uploads.results[0].has_flag("unit"));
+        assert!(!uploads.results[0].is_error());
+        assert!(uploads.results[1].is_error());
+        assert!(uploads.results[1].flags.is_empty());
+    }
+}
@@ -2,3 +2,97 @@
  * Url is just a string that represents a URL. (currently)
  */
 pub type Url = String;
+
+/**
+ * with_query appends query parameters to a url. Parameters with None values are skipped.
+ */
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn with_query(url: &str, params: &[(&str, Option<String>)]) -> Url {
+    let query: Vec<String> = params
+        .iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .map(|value| format!("{}={}", encode(key), encode(value)))
+        })
+        .collect();
+    if query.is_empty() {
+        return url.to_string();
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", url, separator, query.join("&"))
+}
+
+/**
+ * encode_segment percent-encodes a single path segment such as a flag name.
+ */
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn encode_segment(segment: &str) -> String {
+    encode(segment)
+}
+
+/**
+ * encode_path percent-encodes a file path, keeping the slashes between segments.
+ */
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn encode_path(path: &str) -> String {
+    path.split('/')
+        .map(encode)
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(all(test, any(feature = "blocking", feature = "async")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_segment() {
+        assert_eq!(encode_segment("unit"), "unit");
+        assert_eq!(encode_segment("service/api tests"), "service%2Fapi%20tests");
+    }
+
+    #[test]
+    fn test_encode_path() {
+        assert_eq!(encode_path("src/lib.rs"), "src/lib.rs");
+        assert_eq!(encode_path("src/my file#1.rs"), "src/my%20file%231.rs");
+    }
+
+    #[test]
+    fn test_with_query() {
+        let url = "https://codecov.io/api/v2/github/codecov/repos/codecov-demo/compare";
+        assert_eq!(with_query(url, &[("pullid", None)]), url);
+        assert_eq!(
+            with_query(
+                url,
+                &[
+                    ("base", Some("abc".to_string())),
+                    ("head", None),
+                    ("path", Some("src/a b.rs".to_string()))
+                ]
+            ),
+            format!("{}?base=abc&path=src%2Fa%20b.rs", url)
+        );
+        assert_eq!(
+            with_query(
+                "https://example.com?page=2",
+                &[("state", Some("open".to_string()))]
+            ),
+            "https://example.com?page=2&state=open"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+/**
+ * Codecov v2 API
+ * /{service}/{owner}/users endpoints list the users of an owner and manage their seat activation.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::pagination::Paginated;
+use crate::service::Service;
+
+/**
+ * UsersAPIResponse is a page of users returned by the users API.
+ */
+pub type UsersAPIResponse = Paginated<User>;
+
+/**
+ * User is a struct that represents a user of an owner.
+ * activated is true when the user occupies a seat.
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct User {
+    pub service: Service,
+    pub username: Option<String>,
+    pub name: Option<String>,
+    pub activated: bool,
+    pub is_admin: bool,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+/**
+ * UsersQuery holds the filters of the users API.
+ * search matches the username, the name and the email.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsersQuery {
+    pub activated: Option<bool>,
+    pub is_admin: Option<bool>,
+    pub search: Option<String>,
+}
+
+impl UsersQuery {
+    pub fn new() -> UsersQuery {
+        UsersQuery::default()
+    }
+
+    pub fn activated(mut self, activated: bool) -> UsersQuery {
+        self.activated = Some(activated);
+        self
+    }
+
+    pub fn is_admin(mut self, is_admin: bool) -> UsersQuery {
+        self.is_admin = Some(is_admin);
+        self
+    }
+
+    pub fn search(mut self, search: &str) -> UsersQuery {
+        self.search = Some(search.to_string());
+        self
+    }
+
+    /**
+     * query returns the query parameters of the users API.
+     */
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn query(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("activated", self.activated.map(|v| v.to_string())),
+            ("is_admin", self.is_admin.map(|v| v.to_string())),
+            ("search", self.search.clone()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_query() {
+        let query = UsersQuery::new().activated(false).search("kitsu");
+        assert_eq!(
+            query.query(),
+            vec![
+                ("activated", Some("false".to_string())),
+                ("is_admin", None),
+                ("search", Some("kitsu".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_users() {
+        let j = json!({
+            "count": 1,
+            "next": null,
+            "previous": null,
+            "results": [
+                {
+                    "service": "github",
+                    "username": "kitsuyui",
+                    "name": "Yui Kitsu",
+                    "activated": true,
+                    "is_admin": false,
+                    "email": null
+                }
+            ],
+            "total_pages": 1
+        });
+        let users = serde_json::from_value::<UsersAPIResponse>(j).unwrap();
+        assert_eq!(users.results[0].username.as_deref(), Some("kitsuyui"));
+        assert!(users.results[0].activated);
+    }
+}